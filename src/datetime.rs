@@ -0,0 +1,992 @@
+//! Typed date and time values.
+//!
+//! The values of the `date`, `time`, `date-time`, `date-and-or-time`, `timestamp` and `utc-offset` value types are
+//! stored as strings in [`PropertyValue::String`], in the extended format of
+//! [RFC 7095, Section 3.5](https://datatracker.ietf.org/doc/html/rfc7095#section-3.5). The types in this module parse
+//! and produce those strings, including the reduced accuracy (`1985-04`, `10:22`) and truncated (`--04-12`,
+//! `-22:00`) forms. A `timestamp` is a [`DateTime`] with a complete date and time.
+//!
+//! The values are checked against the declared [`Property::value_type`] when a [`Property`] is deserialized.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use vicardi::datetime::*;
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let bday: Property = serde_json::from_value(json!(["bday", {}, "date-and-or-time", "--04-12"]))?;
+//! let date = Date::new(None, Some(4), Some(12)).unwrap();
+//! assert_eq!(bday.datetime(), Some(Ok(DateAndOrTime::Date(date))));
+//!
+//! let time: DateAndOrTime = "T10:22".parse()?;
+//! let DateAndOrTime::Time(time) = time else { unreachable!() };
+//! assert_eq!((time.hour(), time.minute(), time.second()), (Some(10), Some(22), None));
+//!
+//! // Typed values are written with their extended format
+//! let rev = Property::new("rev", None, ValueType::Timestamp, "1995-10-31T22:27:10Z".parse::<DateTime>()?);
+//! assert_eq!(serde_json::to_value(&rev)?, json!(["rev", {}, "timestamp", "1995-10-31T22:27:10Z"]));
+//!
+//! // The value must match the value type
+//! assert!(serde_json::from_value::<Property>(json!(["bday", {}, "date", "1985-04-12T10:22"])).is_err());
+//! assert!(serde_json::from_value::<Property>(json!(["rev", {}, "timestamp", "19951031T222710Z"])).is_err());
+//! assert!(serde_json::from_value::<Property>(json!(["x-a", {}, "time", "1é1"])).is_err());
+//! assert!(serde_json::from_value::<Property>(json!(["x-a", {}, "utc-offset", "+1é1"])).is_err());
+//! assert!("T1é1".parse::<DateAndOrTime>().is_err());
+//! assert!(serde_json::from_value::<Property>(json!(["bday", {}, "date", "2023-02-31"])).is_err());
+//! assert!(Vcard::from_vcf("BEGIN:VCARD\r\nVERSION:4.0\r\nFN:A\r\nX-A;VALUE=time:1é1\r\nEND:VCARD\r\n").is_err());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Cargo features
+//!
+//! - `chrono`: conversions between these types and the `chrono` date and time types.
+//! - `time`: conversions between these types and the `time` crate's date and time types.
+//!
+//! Conversions to those types fail with [`DateTimeError::Incomplete`] when the value is missing a component the
+//! target type requires, e.g. the year of `--04-12`. Missing minutes and seconds of a reduced accuracy time are zero.
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{Property, PropertyValue, ValueType};
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DateTimeError {
+    #[error("invalid {value_type} value {value:?}")]
+    Invalid {
+        value_type: ValueType,
+        value: String,
+    },
+    #[error("{value} has no {component}")]
+    Incomplete {
+        value: String,
+        component: &'static str,
+    },
+    #[error("{0} is out of range")]
+    OutOfRange(String),
+}
+
+impl DateTimeError {
+    /// A stable, kebab-case identifier of the variant, returned by [`Error::code`](crate::Error::code).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Invalid { .. } => "invalid-datetime",
+            Self::Incomplete { .. } => "incomplete-datetime",
+            Self::OutOfRange(_) => "datetime-out-of-range",
+        }
+    }
+}
+
+/// A complete, reduced accuracy (`1985-04`, `1985`) or truncated (`--04-12`, `--04`, `---12`) date.
+///
+/// ```rust
+/// # use vicardi::datetime::*;
+/// # fn main() -> anyhow::Result<()> {
+/// let date: Date = "1985-04".parse()?;
+/// assert_eq!((date.year(), date.month(), date.day()), (Some(1985), Some(4), None));
+/// let date: Date = "--04-15".parse()?;
+/// assert_eq!((date.year(), date.month(), date.day()), (None, Some(4), Some(15)));
+/// let date: Date = "---15".parse()?;
+/// assert_eq!((date.year(), date.month(), date.day()), (None, None, Some(15)));
+/// assert_eq!("19850412".parse::<Date>()?, "1985-04-12".parse()?);
+///
+/// // The day must exist in the month, February 29 only in leap years
+/// assert!("2024-02-29".parse::<Date>().is_ok());
+/// assert!("--02-29".parse::<Date>().is_ok());
+/// assert!("2023-02-29".parse::<Date>().is_err());
+/// assert!("2023-02-31".parse::<Date>().is_err());
+/// assert!("1900-02-29".parse::<Date>().is_err());
+/// assert!("--04-31".parse::<Date>().is_err());
+/// assert!("---32".parse::<Date>().is_err());
+///
+/// // Other malformed dates
+/// assert!("1985-13".parse::<Date>().is_err());
+/// assert!("85-04-12".parse::<Date>().is_err());
+/// assert!("1985-0412".parse::<Date>().is_err());
+/// assert!(Date::new(Some(1985), None, Some(12)).is_none());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Date {
+    year: Option<u16>,
+    month: Option<u8>,
+    day: Option<u8>,
+}
+
+/// A complete, reduced accuracy (`10:22`, `10`) or truncated (`-22:00`, `-22`, `--00`) time, with an optional zone.
+///
+/// ```rust
+/// # use vicardi::datetime::*;
+/// # fn main() -> anyhow::Result<()> {
+/// let time: Time = "10:22".parse()?;
+/// assert_eq!((time.hour(), time.minute(), time.second()), (Some(10), Some(22), None));
+/// let time: Time = "-22".parse()?;
+/// assert_eq!((time.hour(), time.minute(), time.second()), (None, Some(22), None));
+/// let time: Time = "--00".parse()?;
+/// assert_eq!((time.hour(), time.minute(), time.second()), (None, None, Some(0)));
+/// let time: Time = "10:22:00Z".parse()?;
+/// assert_eq!(time.zone(), Some(Zone::Utc));
+///
+/// // A date-and-or-time marks a time with a leading T
+/// let DateAndOrTime::Time(time) = "T10:22".parse()? else { unreachable!() };
+/// assert_eq!((time.hour(), time.minute()), (Some(10), Some(22)));
+/// let DateAndOrTime::Time(time) = "T-22".parse()? else { unreachable!() };
+/// assert_eq!((time.hour(), time.minute()), (None, Some(22)));
+///
+/// assert!("24:00".parse::<Time>().is_err());
+/// assert!("10:60".parse::<Time>().is_err());
+/// assert!("10:22:61".parse::<Time>().is_err());
+/// assert!("1:22".parse::<Time>().is_err());
+/// assert!("10:22+25".parse::<Time>().is_err());
+/// assert!("10-22".parse::<DateAndOrTime>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Time {
+    hour: Option<u8>,
+    minute: Option<u8>,
+    second: Option<u8>,
+    zone: Option<Zone>,
+}
+
+/// A date with a day and a time with an hour, e.g. `1985-04-12T10:22`, `--04-12T10` or `1985-04-12T10:22:00Z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    date: Date,
+    time: Time,
+}
+
+/// The value of a `date-and-or-time` property, a time is written with a leading `T` (`T10:22`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateAndOrTime {
+    Date(Date),
+    DateTime(DateTime),
+    Time(Time),
+}
+
+/// The zone of a [`Time`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Zone {
+    /// `Z`
+    Utc,
+    Offset(UtcOffset),
+}
+
+/// An offset from UTC such as `-05:00` or `+01`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UtcOffset {
+    negative: bool,
+    hours: u8,
+    minutes: Option<u8>,
+}
+
+/// Checks that the present components are contiguous and that at least one is present, e.g. a year and a day
+/// without a month is not a valid date.
+fn is_contiguous(first: bool, second: bool, third: bool) -> bool {
+    !matches!(
+        (first, second, third),
+        (false, false, false) | (true, false, true)
+    )
+}
+
+/// The number of days in the month, 31 if the month is unknown and 29 in February if the year is unknown.
+fn days_in_month(year: Option<u16>, month: Option<u8>) -> u8 {
+    let leap = year.is_none_or(|year| year % 4 == 0 && (year % 100 != 0 || year % 400 == 0));
+    match month {
+        Some(4 | 6 | 9 | 11) => 30,
+        Some(2) if leap => 29,
+        Some(2) => 28,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// Returns [`None`] if a component is out of range or if the components do not form a valid reduced or
+    /// truncated date. The day must exist in the month, February 29 only in leap years if the year is known.
+    pub fn new(year: Option<u16>, month: Option<u8>, day: Option<u8>) -> Option<Self> {
+        let valid = is_contiguous(year.is_some(), month.is_some(), day.is_some())
+            && year.is_none_or(|year| year <= 9999)
+            && month.is_none_or(|month| (1..=12).contains(&month))
+            && day.is_none_or(|day| (1..=days_in_month(year, month)).contains(&day));
+
+        valid.then_some(Self { year, month, day })
+    }
+
+    pub fn year(&self) -> Option<u16> {
+        self.year
+    }
+
+    pub fn month(&self) -> Option<u8> {
+        self.month
+    }
+
+    pub fn day(&self) -> Option<u8> {
+        self.day
+    }
+
+    /// Whether the date has a year, a month and a day.
+    pub fn is_complete(&self) -> bool {
+        self.year.is_some() && self.month.is_some() && self.day.is_some()
+    }
+}
+
+impl Time {
+    /// Returns [`None`] if a component is out of range or if the components do not form a valid reduced or
+    /// truncated time.
+    pub fn new(
+        hour: Option<u8>,
+        minute: Option<u8>,
+        second: Option<u8>,
+        zone: Option<Zone>,
+    ) -> Option<Self> {
+        let valid = is_contiguous(hour.is_some(), minute.is_some(), second.is_some())
+            && hour.is_none_or(|hour| hour <= 23)
+            && minute.is_none_or(|minute| minute <= 59)
+            // 60 is a leap second
+            && second.is_none_or(|second| second <= 60);
+
+        valid.then_some(Self {
+            hour,
+            minute,
+            second,
+            zone,
+        })
+    }
+
+    pub fn hour(&self) -> Option<u8> {
+        self.hour
+    }
+
+    pub fn minute(&self) -> Option<u8> {
+        self.minute
+    }
+
+    pub fn second(&self) -> Option<u8> {
+        self.second
+    }
+
+    pub fn zone(&self) -> Option<Zone> {
+        self.zone
+    }
+
+    /// Whether the time has an hour, a minute and a second.
+    pub fn is_complete(&self) -> bool {
+        self.hour.is_some() && self.minute.is_some() && self.second.is_some()
+    }
+}
+
+impl DateTime {
+    /// Returns [`None`] if the date has no day or the time has no hour.
+    pub fn new(date: Date, time: Time) -> Option<Self> {
+        (date.day.is_some() && time.hour.is_some()).then_some(Self { date, time })
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    /// Whether both the date and the time are complete, as required by the `timestamp` value type.
+    pub fn is_complete(&self) -> bool {
+        self.date.is_complete() && self.time.is_complete()
+    }
+}
+
+impl UtcOffset {
+    /// Returns [`None`] if the hours or minutes are out of range.
+    pub fn new(negative: bool, hours: u8, minutes: Option<u8>) -> Option<Self> {
+        let valid = hours <= 23 && minutes.is_none_or(|minutes| minutes <= 59);
+        valid.then_some(Self {
+            negative,
+            hours,
+            minutes,
+        })
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn hours(&self) -> u8 {
+        self.hours
+    }
+
+    pub fn minutes(&self) -> Option<u8> {
+        self.minutes
+    }
+
+    /// The offset in seconds, negative west of UTC.
+    pub fn seconds(&self) -> i32 {
+        let seconds = i32::from(self.hours) * 3600 + i32::from(self.minutes.unwrap_or(0)) * 60;
+        if self.negative {
+            -seconds
+        } else {
+            seconds
+        }
+    }
+}
+
+impl DateAndOrTime {
+    /// Parses a value of the given date and/or time value type, which must be in the extended format of jCard.
+    ///
+    /// `date` and `time` values only accept a [`DateAndOrTime::Date`] and a [`DateAndOrTime::Time`] (without the
+    /// leading `T`), `date-time` and `timestamp` values a [`DateAndOrTime::DateTime`], complete for a timestamp.
+    pub fn parse_as(value_type: &ValueType, value: &str) -> Result<Self, DateTimeError> {
+        let invalid = || DateTimeError::Invalid {
+            value_type: value_type.clone(),
+            value: value.to_string(),
+        };
+
+        let parsed = match value_type {
+            ValueType::Date => Self::Date(value.parse().map_err(|_| invalid())?),
+            ValueType::Time => Self::Time(value.parse().map_err(|_| invalid())?),
+            ValueType::DateTime => Self::DateTime(value.parse().map_err(|_| invalid())?),
+            ValueType::Timestamp => match value.parse::<DateTime>() {
+                Ok(datetime) if datetime.is_complete() => Self::DateTime(datetime),
+                _ => return Err(invalid()),
+            },
+            ValueType::DateAndOrTime => value.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        };
+
+        // Values are parsed in either format, jCard requires the extended one
+        let extended = match &parsed {
+            Self::Time(time) if *value_type == ValueType::Time => time.to_string(),
+            parsed => parsed.to_string(),
+        };
+        match extended == value {
+            true => Ok(parsed),
+            false => Err(invalid()),
+        }
+    }
+}
+
+impl Property {
+    /// Parses the value of a property with a `date`, `time`, `date-time`, `date-and-or-time` or `timestamp` value
+    /// type. Returns [`None`] for other value types or if the value is not a single string.
+    pub fn datetime(&self) -> Option<Result<DateAndOrTime, DateTimeError>> {
+        match (&self.value_type, self.values.as_slice()) {
+            (
+                ValueType::Date
+                | ValueType::Time
+                | ValueType::DateTime
+                | ValueType::DateAndOrTime
+                | ValueType::Timestamp,
+                [PropertyValue::String(value)],
+            ) => Some(DateAndOrTime::parse_as(&self.value_type, value)),
+            _ => None,
+        }
+    }
+}
+
+/// Checks the string values of a date and time value type, other values are always valid.
+pub(crate) fn validate(
+    value_type: &ValueType,
+    values: &[PropertyValue],
+) -> Result<(), DateTimeError> {
+    let strings = values.iter().filter_map(|value| match value {
+        PropertyValue::String(string) => Some(string),
+        _ => None,
+    });
+
+    for value in strings {
+        match value_type {
+            ValueType::UtcOffset => {
+                let offset: UtcOffset = value.parse()?;
+                if offset.to_string() != *value {
+                    return Err(DateTimeError::Invalid {
+                        value_type: value_type.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+            value_type if is_datetime(value_type.as_str()) => {
+                DateAndOrTime::parse_as(value_type, value)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn is_datetime(value_type: &str) -> bool {
+    matches!(
+        value_type,
+        "date" | "time" | "date-time" | "date-and-or-time" | "timestamp" | "utc-offset"
+    )
+}
+
+/// Converts a date and/or time value between the basic format of RFC 6350 and the extended format of RFC 7095.
+///
+/// Values in either format are accepted, the output is in the extended format if `extended` is `true`.
+pub(crate) fn datetime_format(value_type: &str, value: &str, extended: bool) -> Option<String> {
+    fn format(value: impl Display, extended: bool) -> String {
+        match extended {
+            true => format!("{value}"),
+            false => format!("{value:#}"),
+        }
+    }
+
+    Some(match value_type {
+        "date" => format(value.parse::<Date>().ok()?, extended),
+        "time" => format(value.parse::<Time>().ok()?, extended),
+        "date-time" | "timestamp" => format(value.parse::<DateTime>().ok()?, extended),
+        "date-and-or-time" => format(value.parse::<DateAndOrTime>().ok()?, extended),
+        "utc-offset" => format(value.parse::<UtcOffset>().ok()?, extended),
+        _ => value.to_string(),
+    })
+}
+
+fn number<T: FromStr>(digits: &str, len: usize) -> Option<T> {
+    let valid = digits.len() == len && digits.bytes().all(|b| b.is_ascii_digit());
+    valid.then(|| digits.parse().ok())?
+}
+
+/// Splits `hhmmss` style digits (with or without colons) into two digit numbers.
+fn pairs(value: &str, max: usize) -> Option<Vec<u8>> {
+    let digits = value.replace(':', "");
+    if !digits.is_ascii()
+        || digits.is_empty()
+        || !digits.len().is_multiple_of(2)
+        || digits.len() / 2 > max
+    {
+        return None;
+    }
+
+    (0..digits.len() / 2)
+        .map(|i| number(&digits[i * 2..i * 2 + 2], 2))
+        .collect()
+}
+
+impl FromStr for Date {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DateTimeError::Invalid {
+            value_type: ValueType::Date,
+            value: s.to_string(),
+        };
+
+        if !s.is_ascii() {
+            return Err(invalid());
+        }
+
+        let date = if let Some(day) = s.strip_prefix("---") {
+            Date::new(None, None, Some(number(day, 2).ok_or_else(invalid)?))
+        } else if let Some(rest) = s.strip_prefix("--") {
+            let rest = rest.strip_prefix('-').unwrap_or(rest);
+            let separated = rest.len() == 5 && rest.as_bytes()[2] == b'-';
+            match pairs(&rest.replace('-', ""), 2)
+                .ok_or_else(invalid)?
+                .as_slice()
+            {
+                [month] if rest.len() == 2 => Date::new(None, Some(*month), None),
+                [month, day] if rest.len() == 4 || separated => {
+                    Date::new(None, Some(*month), Some(*day))
+                }
+                _ => None,
+            }
+        } else {
+            let year = s
+                .get(..4)
+                .and_then(|year| number(year, 4))
+                .ok_or_else(invalid)?;
+            let rest = &s.as_bytes()[4..];
+            let (month, day) = match rest.len() {
+                0 => (None, None),
+                // YYYY-MM
+                3 if rest[0] == b'-' => (Some(&s[5..7]), None),
+                // YYYYMMDD
+                4 => (Some(&s[4..6]), Some(&s[6..8])),
+                // YYYY-MM-DD
+                6 if rest[0] == b'-' && rest[3] == b'-' => (Some(&s[5..7]), Some(&s[8..10])),
+                _ => return Err(invalid()),
+            };
+
+            let month = month
+                .map(|month| number(month, 2).ok_or_else(invalid))
+                .transpose()?;
+            let day = day
+                .map(|day| number(day, 2).ok_or_else(invalid))
+                .transpose()?;
+            Date::new(Some(year), month, day)
+        };
+
+        date.ok_or_else(invalid)
+    }
+}
+
+impl FromStr for Time {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DateTimeError::Invalid {
+            value_type: ValueType::Time,
+            value: s.to_string(),
+        };
+
+        let dashes = s.len() - s.trim_start_matches('-').len();
+        let zone_start = s[dashes..]
+            .find(['Z', 'z', '+', '-'])
+            .map_or(s.len(), |i| i + dashes);
+        let zone = match &s[zone_start..] {
+            "" => None,
+            zone => Some(zone.parse().map_err(|_| invalid())?),
+        };
+
+        let max = 3usize.checked_sub(dashes).ok_or_else(invalid)?;
+        let mut components = pairs(&s[dashes..zone_start], max)
+            .ok_or_else(invalid)?
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut all = vec![None; dashes];
+        all.append(&mut components);
+        all.resize(3, None);
+
+        Time::new(all[0], all[1], all[2], zone).ok_or_else(invalid)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DateTimeError::Invalid {
+            value_type: ValueType::DateTime,
+            value: s.to_string(),
+        };
+
+        let (date, time) = s.split_once(['T', 't']).ok_or_else(invalid)?;
+        let date = date.parse().map_err(|_| invalid())?;
+        let time = time.parse().map_err(|_| invalid())?;
+        DateTime::new(date, time).ok_or_else(invalid)
+    }
+}
+
+impl FromStr for DateAndOrTime {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DateTimeError::Invalid {
+            value_type: ValueType::DateAndOrTime,
+            value: s.to_string(),
+        };
+
+        let parsed = match s.split_once(['T', 't']) {
+            Some(("", time)) => time.parse().map(Self::Time),
+            Some(_) => s.parse().map(Self::DateTime),
+            None => s.parse().map(Self::Date),
+        };
+        parsed.map_err(|_| invalid())
+    }
+}
+
+impl FromStr for Zone {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Z" | "z" => Ok(Zone::Utc),
+            offset => offset.parse().map(Zone::Offset),
+        }
+    }
+}
+
+impl FromStr for UtcOffset {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DateTimeError::Invalid {
+            value_type: ValueType::UtcOffset,
+            value: s.to_string(),
+        };
+
+        let negative = match s.get(..1) {
+            Some("+") => false,
+            Some("-") => true,
+            _ => return Err(invalid()),
+        };
+        let valid_colon = s.len() != 6 || s.as_bytes()[3] == b':';
+        let offset = match pairs(&s[1..], 2).filter(|_| valid_colon) {
+            Some(pairs) => match pairs.as_slice() {
+                [hours] => UtcOffset::new(negative, *hours, None),
+                [hours, minutes] => UtcOffset::new(negative, *hours, Some(*minutes)),
+                _ => None,
+            },
+            None => None,
+        };
+
+        offset.ok_or_else(invalid)
+    }
+}
+
+/// Writes two digit components, with `separator` between them. Missing leading components are written as `-`.
+fn write_components(
+    f: &mut std::fmt::Formatter<'_>,
+    components: &[Option<u8>],
+    separator: &str,
+) -> std::fmt::Result {
+    let mut written = false;
+    for component in components {
+        match (component, written) {
+            (Some(value), true) => write!(f, "{separator}{value:02}")?,
+            (Some(value), false) => write!(f, "{value:02}")?,
+            (None, false) => write!(f, "-")?,
+            (None, true) => break,
+        }
+        written |= component.is_some();
+    }
+    Ok(())
+}
+
+/// The extended format, or the basic format with the alternate flag (`{:#}`).
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let separator = if f.alternate() { "" } else { "-" };
+        match self.year {
+            Some(year) => {
+                write!(f, "{year:04}")?;
+                match (self.month, self.day) {
+                    (Some(month), Some(day)) => {
+                        write!(f, "{separator}{month:02}{separator}{day:02}")
+                    }
+                    (Some(month), None) => write!(f, "-{month:02}"),
+                    _ => Ok(()),
+                }
+            }
+            None => {
+                write!(f, "-")?;
+                write_components(f, &[None, self.month, self.day], separator)
+            }
+        }
+    }
+}
+
+/// The extended format, or the basic format with the alternate flag (`{:#}`).
+impl Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let separator = if f.alternate() { "" } else { ":" };
+        write_components(f, &[self.hour, self.minute, self.second], separator)?;
+        match self.zone {
+            Some(zone) if f.alternate() => write!(f, "{zone:#}"),
+            Some(zone) => write!(f, "{zone}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The extended format, or the basic format with the alternate flag (`{:#}`).
+impl Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.alternate() {
+            true => write!(f, "{:#}T{:#}", self.date, self.time),
+            false => write!(f, "{}T{}", self.date, self.time),
+        }
+    }
+}
+
+/// The extended format, or the basic format with the alternate flag (`{:#}`).
+impl Display for DateAndOrTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self, f.alternate()) {
+            (Self::Date(date), true) => write!(f, "{date:#}"),
+            (Self::Date(date), false) => write!(f, "{date}"),
+            (Self::DateTime(datetime), true) => write!(f, "{datetime:#}"),
+            (Self::DateTime(datetime), false) => write!(f, "{datetime}"),
+            (Self::Time(time), true) => write!(f, "T{time:#}"),
+            (Self::Time(time), false) => write!(f, "T{time}"),
+        }
+    }
+}
+
+/// The extended format, or the basic format with the alternate flag (`{:#}`).
+impl Display for Zone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Zone::Utc => write!(f, "Z"),
+            Zone::Offset(offset) if f.alternate() => write!(f, "{offset:#}"),
+            Zone::Offset(offset) => write!(f, "{offset}"),
+        }
+    }
+}
+
+/// The extended format, or the basic format with the alternate flag (`{:#}`).
+impl Display for UtcOffset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.negative { '-' } else { '+' };
+        write!(f, "{sign}{:02}", self.hours)?;
+        match (self.minutes, f.alternate()) {
+            (Some(minutes), true) => write!(f, "{minutes:02}"),
+            (Some(minutes), false) => write!(f, ":{minutes:02}"),
+            (None, _) => Ok(()),
+        }
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn incomplete(value: impl Display, component: &'static str) -> DateTimeError {
+    DateTimeError::Incomplete {
+        value: value.to_string(),
+        component,
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_conversions {
+    use chrono::{Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, Timelike};
+
+    use super::*;
+
+    impl TryFrom<Date> for NaiveDate {
+        type Error = DateTimeError;
+
+        fn try_from(date: Date) -> Result<Self, Self::Error> {
+            let year = date.year.ok_or_else(|| incomplete(date, "year"))?;
+            let month = date.month.ok_or_else(|| incomplete(date, "month"))?;
+            let day = date.day.ok_or_else(|| incomplete(date, "day"))?;
+            NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+                .ok_or_else(|| DateTimeError::OutOfRange(date.to_string()))
+        }
+    }
+
+    impl TryFrom<NaiveDate> for Date {
+        type Error = DateTimeError;
+
+        fn try_from(date: NaiveDate) -> Result<Self, Self::Error> {
+            let year = u16::try_from(date.year()).ok();
+            Date::new(year, Some(date.month() as u8), Some(date.day() as u8))
+                .filter(|_| year.is_some())
+                .ok_or_else(|| DateTimeError::OutOfRange(date.to_string()))
+        }
+    }
+
+    impl TryFrom<Time> for NaiveTime {
+        type Error = DateTimeError;
+
+        fn try_from(time: Time) -> Result<Self, Self::Error> {
+            let hour = time.hour.ok_or_else(|| incomplete(time, "hour"))?;
+            let minute = time.minute.unwrap_or(0);
+            let second = time.second.unwrap_or(0);
+            NaiveTime::from_hms_opt(hour.into(), minute.into(), second.into())
+                .ok_or_else(|| DateTimeError::OutOfRange(time.to_string()))
+        }
+    }
+
+    impl From<NaiveTime> for Time {
+        fn from(time: NaiveTime) -> Self {
+            Time {
+                hour: Some(time.hour() as u8),
+                minute: Some(time.minute() as u8),
+                second: Some(time.second() as u8),
+                zone: None,
+            }
+        }
+    }
+
+    impl TryFrom<DateTime> for NaiveDateTime {
+        type Error = DateTimeError;
+
+        fn try_from(datetime: DateTime) -> Result<Self, Self::Error> {
+            Ok(NaiveDateTime::new(
+                datetime.date.try_into()?,
+                datetime.time.try_into()?,
+            ))
+        }
+    }
+
+    impl TryFrom<NaiveDateTime> for DateTime {
+        type Error = DateTimeError;
+
+        fn try_from(datetime: NaiveDateTime) -> Result<Self, Self::Error> {
+            Ok(DateTime {
+                date: datetime.date().try_into()?,
+                time: datetime.time().into(),
+            })
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::DateTime<FixedOffset> {
+        type Error = DateTimeError;
+
+        fn try_from(datetime: DateTime) -> Result<Self, Self::Error> {
+            let offset = match datetime.time.zone {
+                Some(Zone::Utc) => FixedOffset::east_opt(0),
+                Some(Zone::Offset(offset)) => FixedOffset::east_opt(offset.seconds()),
+                None => return Err(incomplete(datetime, "zone")),
+            }
+            .ok_or_else(|| DateTimeError::OutOfRange(datetime.to_string()))?;
+
+            NaiveDateTime::try_from(datetime)?
+                .and_local_timezone(offset)
+                .single()
+                .ok_or_else(|| DateTimeError::OutOfRange(datetime.to_string()))
+        }
+    }
+
+    impl<Tz: chrono::TimeZone> TryFrom<chrono::DateTime<Tz>> for DateTime {
+        type Error = DateTimeError;
+
+        fn try_from(datetime: chrono::DateTime<Tz>) -> Result<Self, Self::Error> {
+            let seconds = datetime.offset().fix().local_minus_utc();
+            let mut converted = DateTime::try_from(datetime.naive_local())?;
+            converted.time.zone = Some(match seconds {
+                0 => Zone::Utc,
+                _ => Zone::Offset(datetime.offset().fix().into()),
+            });
+            Ok(converted)
+        }
+    }
+
+    impl From<FixedOffset> for UtcOffset {
+        fn from(offset: FixedOffset) -> Self {
+            let seconds = offset.local_minus_utc();
+            let minutes = seconds.unsigned_abs() / 60;
+            UtcOffset {
+                negative: seconds < 0,
+                hours: (minutes / 60) as u8,
+                minutes: Some((minutes % 60) as u8),
+            }
+        }
+    }
+
+    impl TryFrom<UtcOffset> for FixedOffset {
+        type Error = DateTimeError;
+
+        fn try_from(offset: UtcOffset) -> Result<Self, Self::Error> {
+            FixedOffset::east_opt(offset.seconds())
+                .ok_or_else(|| DateTimeError::OutOfRange(offset.to_string()))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_conversions {
+    use super::*;
+
+    impl TryFrom<Date> for time::Date {
+        type Error = DateTimeError;
+
+        fn try_from(date: Date) -> Result<Self, Self::Error> {
+            let year = date.year.ok_or_else(|| incomplete(date, "year"))?;
+            let month = date.month.ok_or_else(|| incomplete(date, "month"))?;
+            let day = date.day.ok_or_else(|| incomplete(date, "day"))?;
+            let out_of_range = |_| DateTimeError::OutOfRange(date.to_string());
+
+            let month = time::Month::try_from(month).map_err(out_of_range)?;
+            time::Date::from_calendar_date(year.into(), month, day).map_err(out_of_range)
+        }
+    }
+
+    impl TryFrom<time::Date> for Date {
+        type Error = DateTimeError;
+
+        fn try_from(date: time::Date) -> Result<Self, Self::Error> {
+            let year = u16::try_from(date.year()).ok();
+            Date::new(year, Some(date.month().into()), Some(date.day()))
+                .filter(|_| year.is_some())
+                .ok_or_else(|| DateTimeError::OutOfRange(date.to_string()))
+        }
+    }
+
+    impl TryFrom<Time> for time::Time {
+        type Error = DateTimeError;
+
+        fn try_from(value: Time) -> Result<Self, Self::Error> {
+            let hour = value.hour.ok_or_else(|| incomplete(value, "hour"))?;
+            let minute = value.minute.unwrap_or(0);
+            let second = value.second.unwrap_or(0);
+            time::Time::from_hms(hour, minute, second)
+                .map_err(|_| DateTimeError::OutOfRange(value.to_string()))
+        }
+    }
+
+    impl From<time::Time> for Time {
+        fn from(time: time::Time) -> Self {
+            Time {
+                hour: Some(time.hour()),
+                minute: Some(time.minute()),
+                second: Some(time.second()),
+                zone: None,
+            }
+        }
+    }
+
+    impl TryFrom<DateTime> for time::PrimitiveDateTime {
+        type Error = DateTimeError;
+
+        fn try_from(datetime: DateTime) -> Result<Self, Self::Error> {
+            Ok(time::PrimitiveDateTime::new(
+                datetime.date.try_into()?,
+                datetime.time.try_into()?,
+            ))
+        }
+    }
+
+    impl TryFrom<time::PrimitiveDateTime> for DateTime {
+        type Error = DateTimeError;
+
+        fn try_from(datetime: time::PrimitiveDateTime) -> Result<Self, Self::Error> {
+            Ok(DateTime {
+                date: datetime.date().try_into()?,
+                time: datetime.time().into(),
+            })
+        }
+    }
+
+    impl TryFrom<DateTime> for time::OffsetDateTime {
+        type Error = DateTimeError;
+
+        fn try_from(datetime: DateTime) -> Result<Self, Self::Error> {
+            let offset = match datetime.time.zone {
+                Some(Zone::Utc) => time::UtcOffset::UTC,
+                Some(Zone::Offset(offset)) => offset.try_into()?,
+                None => return Err(incomplete(datetime, "zone")),
+            };
+
+            Ok(time::PrimitiveDateTime::try_from(datetime)?.assume_offset(offset))
+        }
+    }
+
+    impl TryFrom<time::OffsetDateTime> for DateTime {
+        type Error = DateTimeError;
+
+        fn try_from(datetime: time::OffsetDateTime) -> Result<Self, Self::Error> {
+            let offset = datetime.offset();
+            let mut converted = DateTime {
+                date: datetime.date().try_into()?,
+                time: datetime.time().into(),
+            };
+            converted.time.zone = Some(match offset.is_utc() {
+                true => Zone::Utc,
+                false => Zone::Offset(offset.into()),
+            });
+            Ok(converted)
+        }
+    }
+
+    impl From<time::UtcOffset> for UtcOffset {
+        fn from(offset: time::UtcOffset) -> Self {
+            UtcOffset {
+                negative: offset.is_negative(),
+                hours: offset.whole_hours().unsigned_abs(),
+                minutes: Some(offset.minutes_past_hour().unsigned_abs()),
+            }
+        }
+    }
+
+    impl TryFrom<UtcOffset> for time::UtcOffset {
+        type Error = DateTimeError;
+
+        fn try_from(offset: UtcOffset) -> Result<Self, Self::Error> {
+            time::UtcOffset::from_whole_seconds(offset.seconds())
+                .map_err(|_| DateTimeError::OutOfRange(offset.to_string()))
+        }
+    }
+}
@@ -1,121 +1,302 @@
+use std::fmt::Display;
+
 use serde::{
-    de::{Error, MapAccess, Unexpected, Visitor},
+    de::{DeserializeSeed, Error, MapAccess, Unexpected, Visitor},
     Deserialize,
 };
 
-use crate::{Parameters, Property, PropertyValue, Vcard};
+use crate::{
+    datetime,
+    lenient::{LenientLog, Raw, RawDeserializer},
+    lossless::{PropertyLayout, ValueLayout, VcardLayout, VersionLayout},
+    LenientVcard, LosslessVcard, Parameters, Property, PropertyName, PropertyValue, Strict,
+    ValueType, Vcard,
+};
 
 impl<'de> Deserialize<'de> for Vcard {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        struct VcardVisitor;
-        impl<'de> Visitor<'de> for VcardVisitor {
-            type Value = Vcard;
+        deserializer.deserialize_seq(VcardVisitor {
+            strict: false,
+            layout: None,
+            lenient: None,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Strict<Vcard> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_seq(VcardVisitor {
+                strict: true,
+                layout: None,
+                lenient: None,
+            })
+            .map(Strict)
+    }
+}
+
+impl<'de> Deserialize<'de> for LosslessVcard {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut layout = VcardLayout::default();
+        let vcard = deserializer.deserialize_seq(VcardVisitor {
+            strict: false,
+            layout: Some(&mut layout),
+            lenient: None,
+        })?;
+        Ok(LosslessVcard { vcard, layout })
+    }
+}
+
+impl<'de> Deserialize<'de> for LenientVcard {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut log = LenientLog::default();
+        let vcard = deserializer.deserialize_seq(VcardVisitor {
+            strict: false,
+            layout: None,
+            lenient: Some(&mut log),
+        })?;
+        // The version property is checked after the others
+        log.diagnostics.sort_by_key(|diagnostic| diagnostic.index);
+        Ok(LenientVcard {
+            vcard,
+            diagnostics: log.diagnostics,
+        })
+    }
+}
+
+/// Deserializes a jCard, recording its layout into `layout` if given.
+///
+/// If `lenient` is given, malformed properties are skipped and reported to it instead of failing.
+struct VcardVisitor<'a> {
+    strict: bool,
+    layout: Option<&'a mut VcardLayout>,
+    lenient: Option<&'a mut LenientLog>,
+}
+
+impl<'de> Visitor<'de> for VcardVisitor<'_> {
+    type Value = Vcard;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an RFC 7095 jCard")
+    }
+
+    fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let header: Option<String> = seq.next_element()?;
+        match header.as_deref() {
+            Some("vcard") => {}
+            Some(other) => {
+                return Err(A::Error::invalid_value(
+                    Unexpected::Str(other),
+                    &r#"a "vcard" header string"#,
+                ))
+            }
+            None => {
+                return Err(A::Error::invalid_value(
+                    Unexpected::Seq,
+                    &r#"a non-empty array starting with a "vcard" header"#,
+                ))
+            }
+        }
+
+        let mut version = String::default();
+        let seed = PropertiesSeed {
+            strict: self.strict,
+            layouts: self
+                .layout
+                .as_deref_mut()
+                .map(|layout| &mut layout.properties),
+            lenient: self.lenient.as_deref_mut(),
+        };
+        let Some(mut properties) = seq.next_element_seed(seed)? else {
+            return Err(A::Error::invalid_value(
+                Unexpected::Seq,
+                &r#"an array of jCard properties as the second element"#,
+            ));
+        };
+
+        if self.strict {
+            check_version(&properties)?;
+        }
+
+        let mut version_index = None;
+        let mut version_values = None;
+
+        for (i, property) in properties.iter_mut().enumerate() {
+            if property.property_name() != PropertyName::Version {
+                continue;
+            }
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("an RFC 7095 jCard")
+            version_index = Some(i);
+            if self.layout.is_some() {
+                version_values = Some(property.values.clone());
             }
 
-            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-            where
-                A: serde::de::SeqAccess<'de>,
-            {
-                let header: Option<String> = seq.next_element()?;
-                match header.as_deref() {
-                    Some("vcard") => {}
-                    Some(other) => {
-                        return Err(A::Error::invalid_value(
-                            Unexpected::Str(other),
-                            &r#"a "vcard" header string"#,
-                        ))
+            version = match version_value::<A::Error>(property) {
+                Ok(version) => version,
+                Err(err) => match self.lenient.as_deref_mut() {
+                    Some(log) => {
+                        log.push(log.indices[i], Some(&property.name), &err);
+                        String::new()
                     }
                     None => {
-                        return Err(A::Error::invalid_value(
-                            Unexpected::Seq,
-                            &r#"a non-empty array starting with a "vcard" header"#,
-                        ))
+                        let path = format!("[1][{i}]");
+                        return Err(located(Some(&path), "", Some(&property.name), err));
+                    }
+                },
+            };
+
+            break;
+        }
+
+        let version_property = version_index.map(|i| (i, properties.remove(i)));
+
+        if let Some(layout) = self.layout {
+            layout.version = match (version_property, version_values) {
+                (Some((index, mut property)), Some(values)) => {
+                    property.values = values;
+                    VersionLayout::Source {
+                        index,
+                        version: version.clone(),
+                        property: Box::new(property),
+                        layout: layout.properties.remove(index),
                     }
                 }
+                _ => VersionLayout::Missing,
+            };
+        }
 
-                let mut version = String::default();
-                let Some(mut properties) = seq.next_element::<Vec<Property>>()? else {
-                    return Err(A::Error::invalid_value(
-                        Unexpected::Seq,
-                        &r#"an array of jCard properties as the second element"#,
-                    ));
-                };
+        Ok(Vcard {
+            version,
+            properties,
+        })
+    }
+}
 
-                let mut version_index = None;
+/// Takes the version string out of a `version` property.
+fn version_value<E: Error>(property: &mut Property) -> Result<String, E> {
+    const VERSION_TYPE: &&str = &"a string version property";
 
-                for (i, property) in properties.iter_mut().enumerate() {
-                    if property.name.to_lowercase().as_str() != "version" {
-                        continue;
-                    }
+    let get_str = |value| match value {
+        PropertyValue::String(s) => Ok(s),
 
-                    version_index = Some(i);
+        PropertyValue::Bool(boolean) => {
+            Err(E::invalid_type(Unexpected::Bool(boolean), VERSION_TYPE))
+        }
 
-                    const VERSION_TYPE: &&str = &"a string version property";
+        PropertyValue::Float(float) => Err(E::invalid_type(Unexpected::Float(float), VERSION_TYPE)),
 
-                    let get_str = |value| match value {
-                        PropertyValue::String(s) => Ok(s),
+        PropertyValue::Integer(int) => Err(E::invalid_type(Unexpected::Signed(int), VERSION_TYPE)),
 
-                        PropertyValue::Bool(boolean) => Err(A::Error::invalid_type(
-                            Unexpected::Bool(boolean),
-                            VERSION_TYPE,
-                        )),
+        PropertyValue::Structured(_) => Err(E::invalid_type(Unexpected::Seq, VERSION_TYPE)),
+    };
 
-                        PropertyValue::Float(float) => Err(A::Error::invalid_type(
-                            Unexpected::Float(float),
-                            VERSION_TYPE,
-                        )),
+    match property.values.as_mut_slice() {
+        [PropertyValue::Structured(structured)] => match structured.as_slice() {
+            [_] => get_str(structured.remove(0)),
 
-                        PropertyValue::Integer(int) => Err(A::Error::invalid_type(
-                            Unexpected::Signed(int),
-                            VERSION_TYPE,
-                        )),
+            [] | [_, _, ..] => Err(E::invalid_length(
+                structured.len(),
+                &"a non-structured version property",
+            )),
+        },
 
-                        PropertyValue::Structured(_) => {
-                            Err(A::Error::invalid_type(Unexpected::Seq, VERSION_TYPE))
-                        }
-                    };
+        [_not_structured] => get_str(property.values.remove(0)),
 
-                    version = match property.values.as_slice() {
-                        [PropertyValue::Structured(structured)] => match structured.as_slice() {
-                            [_] => get_str(property.values.remove(0))?,
+        [] | [_, _, ..] => Err(E::invalid_length(
+            property.values.len(),
+            &"exactly one value in the jCard version property",
+        )),
+    }
+}
 
-                            [] | [_, _, ..] => {
-                                return Err(A::Error::invalid_length(
-                                    property.values.len(),
-                                    &"a non-structured version property",
-                                ))
-                            }
-                        },
+/// Deserializes the array of properties of a jCard, recording their layouts into `layouts` if given.
+///
+/// If `lenient` is given, malformed properties are skipped and reported to it instead of failing.
+struct PropertiesSeed<'a> {
+    strict: bool,
+    layouts: Option<&'a mut Vec<PropertyLayout>>,
+    lenient: Option<&'a mut LenientLog>,
+}
 
-                        [_not_structured] => get_str(property.values.remove(0))?,
+impl<'de> DeserializeSeed<'de> for PropertiesSeed<'_> {
+    type Value = Vec<Property>;
 
-                        [] | [_, _, ..] => {
-                            return Err(A::Error::invalid_length(
-                                property.values.len(),
-                                &"exactly one value in the jCard version property",
-                            ))
-                        }
-                    };
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
 
-                    break;
-                }
+impl<'de> Visitor<'de> for PropertiesSeed<'_> {
+    type Value = Vec<Property>;
 
-                version_index.map(|i| properties.remove(i));
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an array of jCard properties")
+    }
 
-                Ok(Vcard {
-                    version,
-                    properties,
-                })
+    fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut properties = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        if let Some(log) = self.lenient {
+            let mut index = 0;
+            while let Some(mut raw) = seq.next_element::<Raw>()? {
+                let name = raw.property_name().map(str::to_string);
+                raw.repair_parameters(|reason| log.push(index, name.as_deref(), reason));
+
+                let visitor = PropertyVisitor {
+                    strict: self.strict,
+                    layout: None,
+                    path: None,
+                };
+                match visitor.deserialize(RawDeserializer::<serde::de::value::Error>::new(raw)) {
+                    Ok(property) => {
+                        properties.push(property);
+                        log.indices.push(index);
+                    }
+                    Err(err) => log.push(index, name.as_deref(), err),
+                }
+                index += 1;
             }
+            return Ok(properties);
         }
 
-        deserializer.deserialize_seq(VcardVisitor)
+        let mut layout = PropertyLayout::default();
+        while let Some(raw) = seq.next_element::<Raw>()? {
+            let visitor = PropertyVisitor {
+                strict: self.strict,
+                layout: self.layouts.is_some().then_some(&mut layout),
+                path: Some(format!("[1][{}]", properties.len())),
+            };
+            let property = visitor
+                .deserialize(RawDeserializer::<serde::de::value::Error>::new(raw))
+                .map_err(A::Error::custom)?;
+
+            properties.push(property);
+            if let Some(layouts) = self.layouts.as_deref_mut() {
+                layouts.push(std::mem::take(&mut layout));
+            }
+        }
+        Ok(properties)
     }
 }
 
@@ -124,109 +305,408 @@ impl<'de> Deserialize<'de> for Property {
     where
         D: serde::Deserializer<'de>,
     {
-        struct PropertyVisitor;
+        let raw = Raw::deserialize(deserializer)?;
+        PropertyVisitor {
+            strict: false,
+            layout: None,
+            path: Some(String::new()),
+        }
+        .deserialize(RawDeserializer::<serde::de::value::Error>::new(raw))
+        .map_err(D::Error::custom)
+    }
+}
 
-        impl<'de> Visitor<'de> for PropertyVisitor {
-            type Value = Property;
+impl<'de> Deserialize<'de> for Strict<Property> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = Raw::deserialize(deserializer)?;
+        PropertyVisitor {
+            strict: true,
+            layout: None,
+            path: Some(String::new()),
+        }
+        .deserialize(RawDeserializer::<serde::de::value::Error>::new(raw))
+        .map(Strict)
+        .map_err(D::Error::custom)
+    }
+}
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("an RFC 7095 jCard property")
-            }
+/// Deserializes a property, coercing its values to its value type.
+///
+/// Values that cannot be coerced are kept as they are, unless `strict` is set. The layout of the property is recorded
+/// into `layout` if given.
+///
+/// Errors are prefixed with their location under `path`, the path to the property in the jCard, if given. Properties
+/// are buffered before they are deserialized, so that the position appended by the outer deserializer is the only one
+/// in the message.
+struct PropertyVisitor<'a> {
+    strict: bool,
+    layout: Option<&'a mut PropertyLayout>,
+    path: Option<String>,
+}
 
-            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-            where
-                A: serde::de::SeqAccess<'de>,
-            {
-                /// The number of elements before the `values` array starts at this level in the property
-                const FIXED_ELEMENTS: usize = 3;
-
-                let mut counter = 0..;
-                let mut len_err = || {
-                    Err(A::Error::invalid_length(
-                        counter.next().unwrap(),
-                        &"an array of at least 4 elements",
-                    ))
-                };
+impl<'de> DeserializeSeed<'de> for PropertyVisitor<'_> {
+    type Value = Property;
 
-                let Some(name) = seq.next_element()? else {
-                    return len_err();
-                };
-                let Some(MapToOneOrMany(parameters)) = seq.next_element()? else {
-                    return len_err();
-                };
-                let Some(value_type) = seq.next_element()? else {
-                    return len_err();
-                };
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
 
-                let mut values = seq
-                    .size_hint()
-                    .map(|len| Vec::with_capacity(len.saturating_sub(FIXED_ELEMENTS)))
-                    .unwrap_or_default();
+impl<'de> Visitor<'de> for PropertyVisitor<'_> {
+    type Value = Property;
 
-                while let Some(value) = seq.next_element()? {
-                    values.push(value);
-                }
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an RFC 7095 jCard property")
+    }
 
-                if values.is_empty() {
-                    return Err(A::Error::invalid_length(
-                        3,
-                        &"at least one value of the jCard property",
+    fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        /// The number of elements before the `values` array starts at this level in the property
+        const FIXED_ELEMENTS: usize = 3;
+
+        let path = self.path.take();
+        let path = path.as_deref();
+        let len_err = |len: usize, name: Option<&str>| {
+            Err(located(
+                path,
+                "",
+                name,
+                A::Error::invalid_length(len, &"an array of at least 4 elements"),
+            ))
+        };
+
+        let Some(name) = seq
+            .next_element::<String>()
+            .map_err(|err| located(path, "[0]", None, err))?
+        else {
+            return len_err(0, None);
+        };
+        let at = |segment: &str, err| located(path, segment, Some(&name), err);
+
+        let mut key = None;
+        let parameters = seq.next_element_seed(ParametersVisitor {
+            arrays: self.layout.as_deref_mut().map(|layout| &mut layout.arrays),
+            key: Some(&mut key),
+        });
+        let Some(parameters) = parameters.map_err(|err| match &key {
+            Some(key) => at(&format!("[1][{key:?}]"), err),
+            None => at("[1]", err),
+        })?
+        else {
+            return len_err(1, Some(&name));
+        };
+        let Some(value_type) = seq
+            .next_element::<ValueType>()
+            .map_err(|err| at("[2]", err))?
+        else {
+            return len_err(2, Some(&name));
+        };
+
+        if self.strict {
+            check_names(&name, &parameters, &value_type)
+                .map_err(|(segment, err)| at(&segment, err))?;
+        }
+
+        let mut values = seq
+            .size_hint()
+            .map(|len| Vec::with_capacity(len.saturating_sub(FIXED_ELEMENTS)))
+            .unwrap_or_default();
+
+        loop {
+            let segment = format!("[{}]", FIXED_ELEMENTS + values.len());
+            let Some(mut value) = seq.next_element().map_err(|err| at(&segment, err))? else {
+                break;
+            };
+
+            if let Some(layout) = self.layout.as_deref_mut() {
+                layout.values.push(ValueLayout::of(&value));
+            }
+            if let Err(unexpected) = coerce(&value_type, &mut value) {
+                if self.strict {
+                    return Err(at(
+                        &segment,
+                        A::Error::invalid_type(
+                            unexpected,
+                            &format!("a {value_type} value in the {name:?} property").as_str(),
+                        ),
                     ));
                 }
-
-                Ok(Property {
-                    name,
-                    parameters,
-                    value_type,
-                    values,
-                })
             }
+            if self.strict && depth(&value) > 2 {
+                return Err(at(
+                    &segment,
+                    A::Error::invalid_type(
+                        Unexpected::Seq,
+                        &format!(
+                            "a structured value of at most two levels in the {name:?} property"
+                        )
+                        .as_str(),
+                    ),
+                ));
+            }
+            values.push(value);
         }
 
-        struct ParametersVisitor;
-        struct MapToOneOrMany(Parameters);
+        if values.is_empty() {
+            return Err(at(
+                "",
+                A::Error::invalid_length(3, &"at least one value of the jCard property"),
+            ));
+        }
 
-        impl<'de> Deserialize<'de> for MapToOneOrMany {
-            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-            where
-                D: serde::Deserializer<'de>,
-            {
-                deserializer.deserialize_map(ParametersVisitor)
-            }
+        datetime::validate(&value_type, &values)
+            .map_err(|err| located(path, "", Some(&name), err))?;
+
+        if let Some(layout) = self.layout {
+            layout.name.clone_from(&name);
+        }
+
+        Ok(Property {
+            name,
+            parameters,
+            value_type,
+            values,
+        })
+    }
+}
+
+/// Deserializes parameters, recording the ones written as an array into `arrays` if given.
+///
+/// The key of the parameter being deserialized is kept in `key` if given, to locate errors in its value.
+struct ParametersVisitor<'a> {
+    arrays: Option<&'a mut Vec<String>>,
+    key: Option<&'a mut Option<String>>,
+}
+
+impl<'de> Deserialize<'de> for Parameters {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        ParametersVisitor {
+            arrays: None,
+            key: None,
         }
+        .deserialize(deserializer)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ParametersVisitor<'_> {
+    type Value = Parameters;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
 
-        impl<'de> Visitor<'de> for ParametersVisitor {
-            type Value = MapToOneOrMany;
+impl<'de> Visitor<'de> for ParametersVisitor<'_> {
+    type Value = Parameters;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("a map from string to one or multiple strings")
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a map from string to one or multiple strings")
+    }
+
+    fn visit_map<M>(mut self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut map = Parameters::with_capacity(access.size_hint().unwrap_or(0));
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Veclike {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        while let Some(key) = access.next_key::<String>()? {
+            if let Some(current) = self.key.as_deref_mut() {
+                *current = Some(key.clone());
             }
 
-            fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
-            where
-                M: MapAccess<'de>,
-            {
-                let mut map = Parameters::with_capacity(access.size_hint().unwrap_or(0));
-
-                #[derive(Deserialize)]
-                #[serde(untagged)]
-                enum Veclike {
-                    One(String),
-                    Many(Vec<String>),
+            let value = match access.next_value()? {
+                Veclike::One(string) => vec![string],
+                Veclike::Many(many) => {
+                    if let Some(arrays) = self.arrays.as_deref_mut() {
+                        arrays.push(key.clone());
+                    }
+                    many
                 }
+            };
 
-                while let Some((key, value)) = access.next_entry()? {
-                    let value = match value {
-                        Veclike::One(string) => vec![string],
-                        Veclike::Many(many) => many,
-                    };
+            map.insert(key, value);
+        }
 
-                    map.insert(key, value);
-                }
+        if let Some(current) = self.key {
+            *current = None;
+        }
+        Ok(map)
+    }
+}
 
-                Ok(MapToOneOrMany(map))
-            }
+/// Checks that the names in a property are lowercase and that its value type is registered or an `x-` extension.
+///
+/// Errors come with the path to the offending element in the property, e.g. `[2]`.
+fn check_names<E: Error>(
+    name: &str,
+    parameters: &Parameters,
+    value_type: &ValueType,
+) -> Result<(), (String, E)> {
+    let is_lowercase = |name: &str| !name.is_empty() && !name.chars().any(char::is_uppercase);
+
+    if !is_lowercase(name) {
+        return Err((
+            "[0]".into(),
+            E::invalid_value(Unexpected::Str(name), &"a lowercase property name"),
+        ));
+    }
+
+    if let Some(key) = parameters.keys().find(|key| !is_lowercase(key)) {
+        return Err((
+            format!("[1][{key:?}]"),
+            E::invalid_value(
+                Unexpected::Str(key),
+                &format!("a lowercase parameter name in the {name:?} property").as_str(),
+            ),
+        ));
+    }
+
+    match value_type {
+        ValueType::Other(other) if !(is_lowercase(other) && other.starts_with("x-")) => Err((
+            "[2]".into(),
+            E::invalid_value(
+                Unexpected::Str(other),
+                &format!("a registered or x- value type in the {name:?} property").as_str(),
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Prefixes an error with the path to the element it occurred at and the name of its property, e.g.
+/// `[1][42][1]["type"] ("tel"): `.
+///
+/// Errors are returned as they are if `path` is `None`, e.g. in a [`LenientVcard`], which reports the index and name
+/// of the property itself.
+fn located<E: Error>(
+    path: Option<&str>,
+    segment: &str,
+    name: Option<&str>,
+    err: impl Display,
+) -> E {
+    let Some(path) = path else {
+        return E::custom(err);
+    };
+
+    let path = format!("{path}{segment}");
+    match (path.is_empty(), name) {
+        (true, None) => E::custom(err),
+        (true, Some(name)) => E::custom(format_args!("{name:?}: {err}")),
+        (false, None) => E::custom(format_args!("{path}: {err}")),
+        (false, Some(name)) => E::custom(format_args!("{path} ({name:?}): {err}")),
+    }
+}
+
+/// Checks that a jCard has exactly one `version` property of `"4.0"`, as the first property.
+fn check_version<E: Error>(properties: &[Property]) -> Result<(), E> {
+    let mut versions = properties
+        .iter()
+        .enumerate()
+        .filter(|(_, property)| property.property_name() == PropertyName::Version);
+
+    let version = match versions.next() {
+        Some((0, version)) => version,
+        Some((i, _)) => {
+            return Err(E::custom(format_args!(
+                "the version property must be the first property, found it at index {i}"
+            )))
+        }
+        None => return Err(E::missing_field("version")),
+    };
+
+    if let Some((i, _)) = versions.next() {
+        return Err(E::custom(format_args!(
+            "duplicate version property at index {i}"
+        )));
+    }
+
+    if version.value_type != ValueType::Text {
+        return Err(E::invalid_value(
+            Unexpected::Str(version.value_type.as_str()),
+            &"the text value type in the version property",
+        ));
+    }
+
+    match version.values.as_slice() {
+        [PropertyValue::String(version)] if version == "4.0" => Ok(()),
+        [PropertyValue::String(version)] => Err(E::invalid_value(
+            Unexpected::Str(version),
+            &r#"version "4.0""#,
+        )),
+        _ => Err(E::custom(
+            r#"the version property must have a single "4.0" string value"#,
+        )),
+    }
+}
+
+/// The number of nested arrays in a value, e.g. 2 for a structured value with a multi-valued component.
+fn depth(value: &PropertyValue) -> usize {
+    match value {
+        PropertyValue::Structured(components) => {
+            1 + components.iter().map(depth).max().unwrap_or_default()
+        }
+        _ => 0,
+    }
+}
+
+/// Converts a value to the variant of its value type, e.g. an integer of a `float` property to a float.
+///
+/// Returns the value (or the component of a structured value) that does not match the value type.
+fn coerce(value_type: &ValueType, value: &mut PropertyValue) -> Result<(), Unexpected<'static>> {
+    match (value_type, &mut *value) {
+        (_, PropertyValue::Structured(components)) => components
+            .iter_mut()
+            .try_for_each(|component| coerce(value_type, component)),
+        (ValueType::Unknown | ValueType::Other(_), _) => Ok(()),
+        (ValueType::Float, PropertyValue::Float(_))
+        | (ValueType::Integer, PropertyValue::Integer(_))
+        | (ValueType::Boolean, PropertyValue::Bool(_)) => Ok(()),
+        (ValueType::Float, PropertyValue::Integer(integer)) => {
+            *value = PropertyValue::Float(*integer as f64);
+            Ok(())
+        }
+        (ValueType::Integer, PropertyValue::Float(float))
+            if float.fract() == 0.0 && (i64::MIN as f64..=i64::MAX as f64).contains(float) =>
+        {
+            *value = PropertyValue::Integer(*float as i64);
+            Ok(())
         }
-        deserializer.deserialize_seq(PropertyVisitor)
+        (
+            ValueType::Text
+            | ValueType::Uri
+            | ValueType::Date
+            | ValueType::Time
+            | ValueType::DateTime
+            | ValueType::DateAndOrTime
+            | ValueType::Timestamp
+            | ValueType::UtcOffset
+            | ValueType::LanguageTag,
+            PropertyValue::String(_),
+        ) => Ok(()),
+        (_, PropertyValue::String(_)) => Err(Unexpected::Other("a string")),
+        (_, PropertyValue::Bool(boolean)) => Err(Unexpected::Bool(*boolean)),
+        (_, PropertyValue::Integer(integer)) => Err(Unexpected::Signed(*integer)),
+        (_, PropertyValue::Float(float)) => Err(Unexpected::Float(*float)),
     }
 }
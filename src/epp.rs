@@ -0,0 +1,684 @@
+//! EPP contact objects ([RFC 5733](https://datatracker.ietf.org/doc/html/rfc5733)) and their mapping to vCards
+//!
+//! [`Contact::from_epp`] reads the `<contact:infData>` of an `<info>` response or the `<contact:create>` of a
+//! command, and the [`From`] implementation turns a [`Contact`] into a [`Vcard`]:
+//!
+//! - each `<contact:postalInfo>` becomes an `fn`, an `org` and an `adr` property, the `int` one first. When both an
+//!   `int` and a `loc` postal info are present, their properties are alternative representations with `ALTID=1`,
+//!   and [`PostalInfo::language`] becomes a `LANGUAGE` parameter,
+//! - the `<contact:street>` lines become the street component of the `adr` and `<contact:cc>` its `cc`
+//!   parameter ([RFC 8605](https://datatracker.ietf.org/doc/html/rfc8605)),
+//! - `<contact:voice>` and `<contact:fax>` become `tel` URIs with a `type` parameter, and the `x` extension an
+//!   `;ext=` parameter of the URI.
+//!
+//! [`Contact::public_vcard`] honours the `<contact:disclose>` element, and [`Contact::from_vcard`] maps a vCard back.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use vicardi::epp::*;
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let xml = r#"<epp xmlns="urn:ietf:params:xml:ns:epp-1.0"><response><resData>
+//!   <contact:infData xmlns:contact="urn:ietf:params:xml:ns:contact-1.0">
+//!     <contact:id>sh8013</contact:id>
+//!     <contact:postalInfo type="int">
+//!       <contact:name>Ivan Petrov</contact:name>
+//!       <contact:org>Example LLC</contact:org>
+//!       <contact:addr>
+//!         <contact:street>Amir Temur 4</contact:street>
+//!         <contact:street>Floor 2</contact:street>
+//!         <contact:city>Tashkent</contact:city>
+//!         <contact:pc>100000</contact:pc>
+//!         <contact:cc>UZ</contact:cc>
+//!       </contact:addr>
+//!     </contact:postalInfo>
+//!     <contact:postalInfo type="loc">
+//!       <contact:name>Иван Петров</contact:name>
+//!       <contact:addr>
+//!         <contact:street>Амир Темур 4</contact:street>
+//!         <contact:city>Ташкент</contact:city>
+//!         <contact:cc>UZ</contact:cc>
+//!       </contact:addr>
+//!     </contact:postalInfo>
+//!     <contact:voice x="1234">+998.711234567</contact:voice>
+//!     <contact:email>ivan@example.uz</contact:email>
+//!     <contact:disclose flag="0">
+//!       <contact:name type="loc"/>
+//!       <contact:addr type="int"/>
+//!       <contact:addr type="loc"/>
+//!       <contact:voice/>
+//!     </contact:disclose>
+//!   </contact:infData>
+//! </resData></response></epp>"#;
+//!
+//! let contact = Contact::from_epp(xml)?;
+//! assert!(matches!(Contact::from_epp(&xml[..200]), Err(Error::Epp(EppError::Xml { .. }))));
+//! assert_eq!(contact.voice, Some(Phone { number: "+998.711234567".into(), ext: Some("1234".into()) }));
+//!
+//! let vcard = Vcard::from(&contact);
+//! assert_eq!(
+//!     serde_json::to_value(&vcard)?,
+//!     json!(["vcard", [
+//!         ["version", {}, "text", "4.0"],
+//!         ["fn", {"altid": "1"}, "text", "Ivan Petrov"],
+//!         ["org", {"altid": "1"}, "text", "Example LLC"],
+//!         ["adr", {"altid": "1", "cc": "UZ"}, "text",
+//!             ["", "", ["Amir Temur 4", "Floor 2"], "Tashkent", "", "100000", ""]],
+//!         ["fn", {"altid": "1"}, "text", "Иван Петров"],
+//!         ["adr", {"altid": "1", "cc": "UZ"}, "text", ["", "", "Амир Темур 4", "Ташкент", "", "", ""]],
+//!         ["tel", {"type": "voice"}, "uri", "tel:+998.711234567;ext=1234"],
+//!         ["email", {}, "text", "ivan@example.uz"]
+//!     ]])
+//! );
+//!
+//! assert_eq!(
+//!     serde_json::to_value(contact.public_vcard(true))?,
+//!     json!(["vcard", [
+//!         ["version", {}, "text", "4.0"],
+//!         ["fn", {"altid": "1"}, "text", "Ivan Petrov"],
+//!         ["org", {"altid": "1"}, "text", "Example LLC"],
+//!         ["fn", {"altid": "1"}, "text", ""],
+//!         ["email", {}, "text", "ivan@example.uz"]
+//!     ]])
+//! );
+//!
+//! let contact = Contact { disclose: None, ..contact };
+//! assert_eq!(Contact::from_vcard("sh8013", &vcard)?, contact);
+//!
+//! // The org of a loc postal info stays with it
+//! let mut contact = Contact { postal_info: contact.postal_info[..1].to_vec(), ..contact };
+//! contact.postal_info.push(PostalInfo {
+//!     type_: PostalInfoType::Loc,
+//!     name: "Иван Петров".into(),
+//!     org: Some("ООО".into()),
+//!     addr: Addr { city: "Ташкент".into(), cc: "UZ".into(), ..Addr::default() },
+//!     language: Some("ru".into()),
+//! });
+//! contact.postal_info[0].org = None;
+//! assert_eq!(Contact::from_vcard("sh8013", &Vcard::from(&contact))?, contact);
+//!
+//! // The street lines are optional
+//! let xml = r#"<contact:create xmlns:contact="urn:ietf:params:xml:ns:contact-1.0">
+//!   <contact:id>sh8014</contact:id>
+//!   <contact:postalInfo type="int">
+//!     <contact:name>Ivan Petrov</contact:name>
+//!     <contact:addr><contact:city>Tashkent</contact:city><contact:cc>UZ</contact:cc></contact:addr>
+//!   </contact:postalInfo>
+//!   <contact:email>ivan@example.uz</contact:email>
+//! </contact:create>"#;
+//! let contact = Contact::from_epp(xml)?;
+//! let vcard = Vcard::from(&contact);
+//! assert_eq!(
+//!     serde_json::to_value(&vcard)?,
+//!     json!(["vcard", [
+//!         ["version", {}, "text", "4.0"],
+//!         ["fn", {}, "text", "Ivan Petrov"],
+//!         ["adr", {"cc": "UZ"}, "text", ["", "", "", "Tashkent", "", "", ""]],
+//!         ["email", {}, "text", "ivan@example.uz"]
+//!     ]])
+//! );
+//! assert_eq!(Contact::from_vcard("sh8014", &vcard)?, contact);
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt::Display;
+
+use thiserror::Error;
+
+use crate::{
+    xml::{Node, SyntaxError},
+    Parameters, Property, PropertyName, PropertyValue, Telephone, ValueType, Vcard,
+};
+
+/// The XML namespace of EPP contact elements.
+pub const NAMESPACE: &str = "urn:ietf:params:xml:ns:contact-1.0";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum EppError {
+    #[error("invalid XML at byte {position}: {message}")]
+    Xml { position: u64, message: String },
+    #[error("expected a <contact:infData> or <contact:create> element")]
+    MissingContact,
+    #[error("missing <contact:{0}> element")]
+    MissingElement(&'static str),
+    #[error("invalid <contact:{element}> value {value:?}")]
+    InvalidValue {
+        element: &'static str,
+        value: String,
+    },
+    #[error("more than one {0} postal info")]
+    DuplicatePostalInfo(PostalInfoType),
+    #[error("the vCard has no {0} property")]
+    MissingProperty(PropertyName),
+    #[error("the vCard {0} property cannot be mapped to a contact")]
+    UnmappableProperty(PropertyName),
+}
+
+impl EppError {
+    /// A stable, kebab-case identifier of the variant, returned by [`Error::code`](crate::Error::code).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Xml { .. } => "epp-xml",
+            Self::MissingContact => "epp-missing-contact",
+            Self::MissingElement(_) => "epp-missing-element",
+            Self::InvalidValue { .. } => "epp-invalid-value",
+            Self::DuplicatePostalInfo(_) => "epp-duplicate-postal-info",
+            Self::MissingProperty(_) => "epp-missing-property",
+            Self::UnmappableProperty(_) => "epp-unmappable-property",
+        }
+    }
+}
+
+impl From<SyntaxError> for EppError {
+    fn from(error: SyntaxError) -> Self {
+        Self::Xml {
+            position: error.position,
+            message: error.message,
+        }
+    }
+}
+
+/// An EPP contact object.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Contact {
+    pub id: String,
+    /// One or two postal infos of different types, the `int` one first.
+    pub postal_info: Vec<PostalInfo>,
+    pub voice: Option<Phone>,
+    pub fax: Option<Phone>,
+    pub email: String,
+    pub disclose: Option<Disclose>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PostalInfoType {
+    /// Internationalized: restricted to 7-bit US-ASCII.
+    Int,
+    /// Localized: may use any UTF-8 characters.
+    Loc,
+}
+
+impl PostalInfoType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::Loc => "loc",
+        }
+    }
+}
+
+impl Display for PostalInfoType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostalInfo {
+    pub type_: PostalInfoType,
+    pub name: String,
+    pub org: Option<String>,
+    pub addr: Addr,
+    /// The language of the postal info. EPP does not carry it, so it is only set when mapping from a vCard with a
+    /// `LANGUAGE` parameter or by the caller.
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Addr {
+    /// Up to three street lines.
+    pub street: Vec<String>,
+    pub city: String,
+    /// State or province.
+    pub sp: Option<String>,
+    /// Postal code.
+    pub pc: Option<String>,
+    /// ISO 3166 alpha-2 country code.
+    pub cc: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phone {
+    /// The number in the EPP `+CC.NUMBER` format.
+    pub number: String,
+    /// The extension, from the `x` attribute.
+    pub ext: Option<String>,
+}
+
+/// The `<contact:disclose>` element.
+///
+/// With `flag` unset, the listed elements must not be disclosed. With `flag` set, they may be disclosed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Disclose {
+    pub flag: bool,
+    pub name: Vec<PostalInfoType>,
+    pub org: Vec<PostalInfoType>,
+    pub addr: Vec<PostalInfoType>,
+    pub voice: bool,
+    pub fax: bool,
+    pub email: bool,
+}
+
+/// An element that can be listed in a [`Disclose`].
+#[derive(Debug, Clone, Copy)]
+enum Element {
+    Name(PostalInfoType),
+    Org(PostalInfoType),
+    Addr(PostalInfoType),
+    Voice,
+    Fax,
+    Email,
+}
+
+impl Disclose {
+    fn lists(&self, element: Element) -> bool {
+        match element {
+            Element::Name(type_) => self.name.contains(&type_),
+            Element::Org(type_) => self.org.contains(&type_),
+            Element::Addr(type_) => self.addr.contains(&type_),
+            Element::Voice => self.voice,
+            Element::Fax => self.fax,
+            Element::Email => self.email,
+        }
+    }
+}
+
+impl Contact {
+    /// Reads the first `<contact:infData>` or `<contact:create>` element of an EPP document.
+    pub fn from_epp(xml: &str) -> Result<Self, crate::Error> {
+        Ok(Self::read_epp(xml)?)
+    }
+
+    fn read_epp(xml: &str) -> Result<Self, EppError> {
+        let root = Node::parse(xml)?.ok_or(EppError::MissingContact)?;
+        let contact = find_contact(&root).ok_or(EppError::MissingContact)?;
+
+        let mut postal_info = contact
+            .children_named(NAMESPACE, "postalInfo")
+            .map(PostalInfo::from_node)
+            .collect::<Result<Vec<_>, _>>()?;
+        check_postal_info(&mut postal_info)?;
+
+        Ok(Self {
+            id: required(contact, "id")?,
+            postal_info,
+            voice: child(contact, "voice").map(Phone::from_node),
+            fax: child(contact, "fax").map(Phone::from_node),
+            email: required(contact, "email")?,
+            disclose: child(contact, "disclose")
+                .map(Disclose::from_node)
+                .transpose()?,
+        })
+    }
+
+    /// The vCard of the contact without the elements that must not be disclosed.
+    ///
+    /// Elements listed in [`Contact::disclose`] are disclosed according to its flag, other elements according to
+    /// `disclose_by_default`, the server policy. An undisclosed name leaves an empty `fn`, as a vCard requires one.
+    pub fn public_vcard(&self, disclose_by_default: bool) -> Vcard {
+        self.vcard(|element| match &self.disclose {
+            Some(disclose) if disclose.lists(element) => disclose.flag,
+            _ => disclose_by_default,
+        })
+    }
+
+    fn vcard(&self, disclosed: impl Fn(Element) -> bool) -> Vcard {
+        let mut vcard = Vcard::default();
+
+        for info in &self.postal_info {
+            let mut parameters = Parameters::new();
+            if self.postal_info.len() > 1 {
+                parameters.insert("altid".into(), vec!["1".into()]);
+            }
+            if let Some(language) = &info.language {
+                parameters.insert("language".into(), vec![language.clone()]);
+            }
+
+            let name = match disclosed(Element::Name(info.type_)) {
+                true => info.name.as_str(),
+                false => "",
+            };
+            vcard.push(Property::new_fn(name, parameters.clone()));
+
+            if let Some(org) = info
+                .org
+                .as_ref()
+                .filter(|_| disclosed(Element::Org(info.type_)))
+            {
+                vcard.push(Property::new_org(org, parameters.clone()));
+            }
+
+            if disclosed(Element::Addr(info.type_)) {
+                parameters.insert("cc".into(), vec![info.addr.cc.clone()]);
+                vcard.push(Property::new(
+                    "adr",
+                    parameters,
+                    ValueType::Text,
+                    info.addr.to_value(),
+                ));
+            }
+        }
+
+        if let Some(voice) = self.voice.as_ref().filter(|_| disclosed(Element::Voice)) {
+            vcard.push(Property::new_tel(
+                Telephone::Voice,
+                voice.to_uri_number(),
+                None,
+            ));
+        }
+        if let Some(fax) = self.fax.as_ref().filter(|_| disclosed(Element::Fax)) {
+            vcard.push(Property::new_tel(Telephone::Fax, fax.to_uri_number(), None));
+        }
+        if disclosed(Element::Email) {
+            vcard.push(Property::new_email(&self.email, None));
+        }
+
+        vcard
+    }
+
+    /// Maps a vCard back to a contact.
+    ///
+    /// Each `fn` property starts a postal info, and the first `org` and `adr` after it, before the next `fn`, belong
+    /// to it. Properties before the first `fn` belong to the first postal info. A postal info with
+    /// non-ASCII characters is `loc`, otherwise the first one is `int`. The first `tel` with a `fax` type is the fax
+    /// number and the first other `tel` the voice number.
+    pub fn from_vcard(id: impl ToString, vcard: &Vcard) -> Result<Self, crate::Error> {
+        Ok(Self::map_vcard(id, vcard)?)
+    }
+
+    fn map_vcard(id: impl ToString, vcard: &Vcard) -> Result<Self, EppError> {
+        let named = |name: PropertyName| {
+            vcard
+                .properties
+                .iter()
+                .filter(move |property| property.property_name() == name)
+        };
+        // Each `fn` starts a postal info, the `org` and `adr` properties belong to the `fn` before them
+        let mut runs: Vec<(&Property, [Option<&Property>; 2])> = Vec::new();
+        let mut leading = [None; 2];
+        for property in &vcard.properties {
+            let slot = match property.property_name() {
+                PropertyName::Fn => {
+                    runs.push((property, [None; 2]));
+                    continue;
+                }
+                PropertyName::Org => 0,
+                PropertyName::Adr => 1,
+                _ => continue,
+            };
+            let run = runs.last_mut().map_or(&mut leading, |(_, run)| run);
+            run[slot] = run[slot].or(Some(property));
+        }
+        if let Some((_, first)) = runs.first_mut() {
+            first[0] = first[0].or(leading[0]);
+            first[1] = first[1].or(leading[1]);
+        }
+
+        let mut postal_info = Vec::new();
+        for (property, [org, adr]) in runs {
+            let name = property
+                .text()
+                .ok_or(EppError::UnmappableProperty(PropertyName::Fn))?;
+            let org = org
+                .map(|org| {
+                    org.text()
+                        .ok_or(EppError::UnmappableProperty(PropertyName::Org))
+                })
+                .transpose()?;
+            let addr = adr
+                .ok_or(EppError::MissingProperty(PropertyName::Adr))
+                .and_then(Addr::from_property)?;
+
+            let ascii = name.is_ascii() && org.is_none_or(str::is_ascii) && addr.is_ascii();
+            let type_ = match ascii
+                && !postal_info
+                    .iter()
+                    .any(|p: &PostalInfo| p.type_ == PostalInfoType::Int)
+            {
+                true => PostalInfoType::Int,
+                false => PostalInfoType::Loc,
+            };
+
+            postal_info.push(PostalInfo {
+                type_,
+                name: name.to_string(),
+                org: org.map(ToString::to_string),
+                addr,
+                language: property.parameters.language().map(ToString::to_string),
+            });
+        }
+        if postal_info.is_empty() {
+            return Err(EppError::MissingProperty(PropertyName::Fn));
+        }
+        check_postal_info(&mut postal_info)?;
+
+        let is_fax = |tel: &&Property| {
+            tel.parameters
+                .types()
+                .any(|t| t.as_ref().eq_ignore_ascii_case("fax"))
+        };
+        let phone = |tel: Option<&Property>| {
+            tel.map(|tel| {
+                Phone::from_property(tel).ok_or(EppError::UnmappableProperty(PropertyName::Tel))
+            })
+            .transpose()
+        };
+
+        Ok(Self {
+            id: id.to_string(),
+            postal_info,
+            voice: phone(named(PropertyName::Tel).find(|tel| !is_fax(tel)))?,
+            fax: phone(named(PropertyName::Tel).find(is_fax))?,
+            email: named(PropertyName::Email)
+                .next()
+                .ok_or(EppError::MissingProperty(PropertyName::Email))
+                .and_then(|email| {
+                    email
+                        .text()
+                        .ok_or(EppError::UnmappableProperty(PropertyName::Email))
+                })?
+                .to_string(),
+            disclose: None,
+        })
+    }
+}
+
+impl From<&Contact> for Vcard {
+    /// The full vCard of the contact, ignoring [`Contact::disclose`].
+    fn from(contact: &Contact) -> Self {
+        contact.vcard(|_| true)
+    }
+}
+
+impl PostalInfo {
+    fn from_node(node: &Node) -> Result<Self, EppError> {
+        let type_ = postal_info_type(node, "postalInfo")?;
+        let addr = child(node, "addr").ok_or(EppError::MissingElement("addr"))?;
+
+        Ok(Self {
+            type_,
+            name: required(node, "name")?,
+            org: optional(node, "org"),
+            addr: Addr {
+                street: addr
+                    .children_named(NAMESPACE, "street")
+                    .map(|street| street.text.trim().to_string())
+                    .filter(|street| !street.is_empty())
+                    .collect(),
+                city: required(addr, "city")?,
+                sp: optional(addr, "sp"),
+                pc: optional(addr, "pc"),
+                cc: required(addr, "cc")?,
+            },
+            language: None,
+        })
+    }
+}
+
+impl Addr {
+    /// The structured `adr` value, with several street lines as a list in the street component.
+    fn to_value(&self) -> PropertyValue {
+        let street = match self.street.as_slice() {
+            [] => "".into(),
+            [street] => street.into(),
+            streets => PropertyValue::Structured(streets.iter().map(Into::into).collect()),
+        };
+        let optional = |value: &Option<String>| value.as_deref().unwrap_or_default().into();
+
+        PropertyValue::Structured(vec![
+            "".into(),
+            "".into(),
+            street,
+            self.city.as_str().into(),
+            optional(&self.sp),
+            optional(&self.pc),
+            "".into(),
+        ])
+    }
+
+    fn from_property(property: &Property) -> Result<Self, EppError> {
+        let unmappable = || EppError::UnmappableProperty(PropertyName::Adr);
+        let [PropertyValue::Structured(components)] = property.values.as_slice() else {
+            return Err(unmappable());
+        };
+        let [_, _, street, city, sp, pc, country] = components.as_slice() else {
+            return Err(unmappable());
+        };
+
+        let street: Vec<&str> = match street {
+            PropertyValue::Structured(lines) => lines.iter().filter_map(component).collect(),
+            street => component(street).into_iter().collect(),
+        };
+        let cc = match property.parameters.get("cc").map(Vec::as_slice) {
+            Some([cc]) => Some(cc.as_str()),
+            _ => component(country),
+        };
+
+        Ok(Self {
+            street: street.into_iter().map(ToString::to_string).collect(),
+            city: component(city).ok_or_else(unmappable)?.to_string(),
+            sp: component(sp).map(ToString::to_string),
+            pc: component(pc).map(ToString::to_string),
+            cc: cc.ok_or_else(unmappable)?.to_string(),
+        })
+    }
+
+    fn is_ascii(&self) -> bool {
+        self.street.iter().all(|street| street.is_ascii())
+            && self.city.is_ascii()
+            && self.sp.as_deref().is_none_or(str::is_ascii)
+            && self.pc.as_deref().is_none_or(str::is_ascii)
+    }
+}
+
+impl Phone {
+    fn from_node(node: &Node) -> Self {
+        Self {
+            number: node.text.trim().to_string(),
+            ext: node
+                .attribute("x")
+                .filter(|ext| !ext.is_empty())
+                .map(ToString::to_string),
+        }
+    }
+
+    /// The number with the extension as an `;ext=` parameter, the part of a `tel:` URI after the scheme.
+    fn to_uri_number(&self) -> String {
+        match &self.ext {
+            Some(ext) => format!("{};ext={ext}", self.number),
+            None => self.number.clone(),
+        }
+    }
+
+    fn from_property(property: &Property) -> Option<Self> {
+        let (number, ext) = property.tel_number()?;
+        Some(Self {
+            number: number.to_string(),
+            ext: ext.map(ToString::to_string),
+        })
+    }
+}
+
+impl Disclose {
+    fn from_node(node: &Node) -> Result<Self, EppError> {
+        let flag = match node.attribute("flag") {
+            Some("1" | "true") => true,
+            Some("0" | "false") => false,
+            other => {
+                return Err(EppError::InvalidValue {
+                    element: "disclose",
+                    value: other.unwrap_or_default().to_string(),
+                })
+            }
+        };
+        let types = |name: &'static str| {
+            node.children_named(NAMESPACE, name)
+                .map(|child| postal_info_type(child, name))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            flag,
+            name: types("name")?,
+            org: types("org")?,
+            addr: types("addr")?,
+            voice: child(node, "voice").is_some(),
+            fax: child(node, "fax").is_some(),
+            email: child(node, "email").is_some(),
+        })
+    }
+}
+
+fn find_contact(node: &Node) -> Option<&Node> {
+    if node.is_in(NAMESPACE) && matches!(node.name.as_str(), "infData" | "create") {
+        return Some(node);
+    }
+    node.children.iter().find_map(find_contact)
+}
+
+/// Sorts the postal infos `int` first, rejecting duplicate types.
+fn check_postal_info(postal_info: &mut [PostalInfo]) -> Result<(), EppError> {
+    postal_info.sort_by_key(|info| info.type_);
+    match postal_info {
+        [] => Err(EppError::MissingElement("postalInfo")),
+        [first, second, ..] if first.type_ == second.type_ => {
+            Err(EppError::DuplicatePostalInfo(first.type_))
+        }
+        [_, _, third, ..] => Err(EppError::DuplicatePostalInfo(third.type_)),
+        _ => Ok(()),
+    }
+}
+
+fn postal_info_type(node: &Node, element: &'static str) -> Result<PostalInfoType, EppError> {
+    match node.attribute("type") {
+        Some("int") => Ok(PostalInfoType::Int),
+        Some("loc") => Ok(PostalInfoType::Loc),
+        other => Err(EppError::InvalidValue {
+            element,
+            value: other.unwrap_or_default().to_string(),
+        }),
+    }
+}
+
+fn child<'a>(node: &'a Node, name: &'a str) -> Option<&'a Node> {
+    node.children_named(NAMESPACE, name).next()
+}
+
+/// The trimmed text of a child element, or `None` if it is missing or empty.
+fn optional(node: &Node, name: &str) -> Option<String> {
+    child(node, name)
+        .map(|child| child.text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+fn required(node: &Node, name: &'static str) -> Result<String, EppError> {
+    optional(node, name).ok_or(EppError::MissingElement(name))
+}
+
+/// A non-empty string component of a structured value.
+fn component(value: &PropertyValue) -> Option<&str> {
+    match value {
+        PropertyValue::String(value) if !value.is_empty() => Some(value),
+        _ => None,
+    }
+}
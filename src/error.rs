@@ -0,0 +1,148 @@
+//! The crate-level error type
+use thiserror::Error;
+
+use crate::{DateTimeError, EppError, VcfError, Violation, XcardError};
+
+/// An error returned by this crate.
+///
+/// This is the error of the validation and conversion APIs, such as [`Vcard::validate`](crate::Vcard::validate),
+/// [`Vcard::from_vcf`](crate::Vcard::from_vcf), [`Vcard::from_xcard`](crate::Vcard::from_xcard) and
+/// [`Contact::from_epp`](crate::epp::Contact::from_epp). The format modules keep their own error types for the details
+/// of a failure, which are wrapped in the [`Error::Vcf`], [`Error::Xcard`] and [`Error::Epp`] variants, and `FromStr`
+/// implementations such as [`Date`](crate::datetime::Date)'s return those types directly.
+///
+/// Every variant has a stable, machine-readable [`Error::code`]. The variants wrapping the error of a format module
+/// carry the code of the underlying [`VcfErrorKind`](crate::VcfErrorKind), [`XcardError`], [`EppError`] or
+/// [`DateTimeError`]. A [`Error::Validation`] is always `validation`, and each of its violations has its own
+/// [`Violation::code`].
+///
+/// Variants that concern a property carry the index of the property in [`Vcard::properties`](crate::Vcard::properties)
+/// when it is known, i.e. when the error comes from a whole [`Vcard`](crate::Vcard) rather than a single
+/// [`Property`](crate::Property).
+///
+/// Serializing a [`Vcard`](crate::Vcard) reports these errors through [`serde::ser::Error::custom`], so the message
+/// of the serializer's error is the [`Display`](std::fmt::Display) of the variant.
+///
+/// ```rust
+/// # use vicardi::*;
+/// # fn main() -> anyhow::Result<()> {
+/// let mut vcard = Vcard::default();
+/// vcard.push(Property::new_fn("Vicardi", None));
+/// vcard.push(Property::new_multivalued("email", None, ValueType::Text, vec![]));
+///
+/// let error = serde_json::to_string(&vcard).unwrap_err();
+/// assert_eq!(error.to_string(), r#"property 1 ("email"): at least one value must be present"#);
+///
+/// let error = Vcard::default().validate().unwrap_err();
+/// assert_eq!(error.code(), "validation");
+/// assert_eq!(error.to_string(), "invalid vCard: missing required fn property");
+/// let Error::Validation(violations) = error else { unreachable!() };
+/// assert_eq!(violations[0].code(), "missing-property");
+///
+/// let error = Vcard::from_vcf("BEGIN:VCARD\r\nFN:Vicardi\r\nEND:VCARD\r\n").unwrap_err();
+/// assert_eq!(error.code(), "vcf-missing-version");
+///
+/// let error = Address::try_from(vec!["Tashkent".to_string()]).unwrap_err();
+/// assert_eq!(error, Error::InvalidStructuredAddress { components: 1 });
+/// assert_eq!(error.code(), "invalid-structured-address");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A property has no values.
+    #[error("{}at least one value must be present", context(*.index, .name))]
+    MissingValue { index: Option<usize>, name: String },
+    /// A structured value has no components. `name` is `None` when the value is serialized on its own.
+    #[error("{}empty structured value", .name.as_deref().map_or_else(String::new, |name| context(*.index, name)))]
+    EmptyStructuredValue {
+        index: Option<usize>,
+        name: Option<String>,
+    },
+    /// A property has a parameter without values.
+    #[error("{}the {parameter:?} parameter has no values", context(*.index, .name))]
+    EmptyParameter {
+        index: Option<usize>,
+        name: String,
+        parameter: String,
+    },
+    /// A structured address does not have exactly 7 components.
+    #[error("a structured address has 7 components, found {components}")]
+    InvalidStructuredAddress { components: usize },
+    /// A vCard failed [`Vcard::validate`](crate::Vcard::validate).
+    #[error("invalid vCard: {}", join(.0))]
+    Validation(Vec<Violation>),
+    /// A property group that is not made of letters, digits and dashes.
+    #[error("invalid property group {0:?}")]
+    InvalidGroup(String),
+    /// A role that is not in the RDAP JSON Values registry.
+    #[error("unknown RDAP role {0:?}")]
+    UnknownRole(String),
+    /// A status that is not in the RDAP JSON Values registry.
+    #[error("unknown RDAP status {0:?}")]
+    UnknownStatus(String),
+    /// A redaction rule that cannot be applied, identified by its name.
+    #[error("invalid redaction rule {name:?}: {reason}")]
+    InvalidRedactionRule { name: String, reason: &'static str },
+    #[error(transparent)]
+    DateTime(#[from] DateTimeError),
+    #[error(transparent)]
+    Vcf(#[from] VcfError),
+    #[error(transparent)]
+    Xcard(#[from] XcardError),
+    #[error(transparent)]
+    Epp(#[from] EppError),
+}
+
+impl Error {
+    /// A stable, kebab-case identifier of the error.
+    ///
+    /// A [`Error::Validation`] is `validation` whatever its violations, and the variants wrapping the errors of other
+    /// modules have the code of the wrapped error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingValue { .. } => "missing-value",
+            Self::EmptyStructuredValue { .. } => "empty-structured-value",
+            Self::EmptyParameter { .. } => "empty-parameter",
+            Self::InvalidStructuredAddress { .. } => "invalid-structured-address",
+            Self::Validation(_) => "validation",
+            Self::InvalidGroup(_) => "invalid-group",
+            Self::UnknownRole(_) => "unknown-role",
+            Self::UnknownStatus(_) => "unknown-status",
+            Self::InvalidRedactionRule { .. } => "invalid-redaction-rule",
+            Self::DateTime(error) => error.code(),
+            Self::Vcf(error) => error.code(),
+            Self::Xcard(error) => error.code(),
+            Self::Epp(error) => error.code(),
+        }
+    }
+}
+
+impl From<Vec<Violation>> for Error {
+    fn from(violations: Vec<Violation>) -> Self {
+        Self::Validation(violations)
+    }
+}
+
+impl From<Violation> for Error {
+    fn from(violation: Violation) -> Self {
+        Self::Validation(vec![violation])
+    }
+}
+
+/// The `property {index} ({name:?}): ` prefix of a property error.
+fn context(index: Option<usize>, name: &str) -> String {
+    match index {
+        Some(index) => format!("property {index} ({name:?}): "),
+        None => format!("property {name:?}: "),
+    }
+}
+
+fn join(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
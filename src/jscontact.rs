@@ -0,0 +1,742 @@
+//! JSContact ([RFC 9553](https://datatracker.ietf.org/doc/html/rfc9553)) cards and their conversion from and to
+//! [`Vcard`](crate::Vcard), following the mapping rules of [RFC 9555](https://datatracker.ietf.org/doc/html/rfc9555).
+//!
+//! The conversion is lossless as far as JSContact allows:
+//!
+//! - properties without a JSContact equivalent are kept in [`Card::v_card_props`] as jCard arrays,
+//! - parameters without a JSContact equivalent are kept in the `vCardParams` member of the converted object,
+//! - properties sharing an `ALTID` with a `LANGUAGE` parameter become [`Card::localizations`],
+//! - a vCard without a `UID` gets a [`generated_uid`].
+//!
+//! Identifiers of the converted objects are taken from the `PROP-ID` parameter, or numbered in order otherwise.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use vicardi::jscontact::Card;
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new("uid", None, "uri", "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af"));
+//! vcard.push(Property::new_fn("Ivan Petrov", parameters! {"altid" => "1"}));
+//! vcard.push(Property::new_fn("Иван Петров", parameters! {"altid" => "1", "language" => "ru"}));
+//! vcard.push(Property::new_tel(Telephone::Voice, "+998-71-123-45-67", parameters! {"pref" => "1"}));
+//! vcard.push(Property::new("x-karma-points", None, "integer", PropertyValue::Integer(42)));
+//!
+//! let card = Card::from(&vcard);
+//!
+//! let json = json!({
+//!     "@type": "Card",
+//!     "version": "1.0",
+//!     "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+//!     "name": {"full": "Ivan Petrov"},
+//!     "phones": {
+//!         "1": {"number": "tel:+998-71-123-45-67", "features": {"voice": true}, "pref": 1}
+//!     },
+//!     "localizations": {
+//!         "ru": {"name/full": "Иван Петров"}
+//!     },
+//!     "vCardProps": [["x-karma-points", {}, "integer", 42]]
+//! });
+//!
+//! assert_eq!(serde_json::to_value(&card)?, json);
+//! assert_eq!(Vcard::from(&card).properties.len(), vcard.properties.len());
+//!
+//! // The language of the full name becomes the language of the card, and a card always has a UID
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new_fn("Ivan", parameters! {"language" => "uz", "pref" => "1"}));
+//! let card = Card::from(&vcard);
+//! assert_eq!(card.name.as_ref().and_then(|name| name.full.as_deref()), Some("Ivan"));
+//! assert_eq!(card.language.as_deref(), Some("uz"));
+//! assert!(card.v_card_props.is_empty());
+//! assert!(card.uid.starts_with("urn:uuid:"));
+//! assert_eq!(card.uid, Card::from(&vcard).uid);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Names, addresses and phones become their JSContact objects, and their localized alternatives become patches of
+//! those objects. Converting the card back gives the same properties, with `ALTID` values numbered by the card:
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use vicardi::jscontact::Card;
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new("uid", None, "uri", "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af"));
+//! vcard.push(Property::new_fn("Ivan Petrov", parameters! {"altid" => "1"}));
+//! vcard.push(Property::new_fn("Иван Петров", parameters! {"altid" => "1", "language" => "ru"}));
+//! let n = ["Petrov", "Ivan", "", "Dr.", ""].map(PropertyValue::from).to_vec();
+//! vcard.push(Property::new("n", None, ValueType::Text, PropertyValue::Structured(n)));
+//! vcard.push(Property::new_adr(
+//!     ["", "", "Amir Temur 4", "Tashkent", "", "100000", "Uzbekistan"].map(String::from).into(),
+//!     parameters! {"altid" => "2", "type" => "work", "cc" => "UZ"},
+//! ));
+//! vcard.push(Property::new_adr(
+//!     ["", "", "Амир Темур 4", "Ташкент", "", "100000", "Узбекистан"].map(String::from).into(),
+//!     parameters! {"altid" => "2", "type" => "work", "cc" => "UZ", "language" => "ru"},
+//! ));
+//! vcard.push(Property::new_tel(Telephone::Voice, "+998-71-123-45-67", parameters! {"pref" => "1"}));
+//! vcard.push(Property::new("tel", parameters! {"type" => ["home", "fax"]}, "uri", "tel:+998-71-123-45-68"));
+//!
+//! let card = Card::from(&vcard);
+//! let json = serde_json::to_value(&card)?;
+//! assert_eq!(
+//!     json["name"],
+//!     json!({
+//!         "full": "Ivan Petrov",
+//!         "components": [
+//!             {"kind": "surname", "value": "Petrov"},
+//!             {"kind": "given", "value": "Ivan"},
+//!             {"kind": "title", "value": "Dr."}
+//!         ]
+//!     })
+//! );
+//! let address = json!({
+//!     "components": [
+//!         {"kind": "name", "value": "Amir Temur 4"},
+//!         {"kind": "locality", "value": "Tashkent"},
+//!         {"kind": "postcode", "value": "100000"},
+//!         {"kind": "country", "value": "Uzbekistan"}
+//!     ],
+//!     "countryCode": "UZ",
+//!     "contexts": {"work": true}
+//! });
+//! assert_eq!(json["addresses"], json!({"1": address}));
+//! assert_eq!(
+//!     json["phones"],
+//!     json!({
+//!         "1": {"number": "tel:+998-71-123-45-67", "features": {"voice": true}, "pref": 1},
+//!         "2": {"number": "tel:+998-71-123-45-68", "features": {"fax": true}, "contexts": {"private": true}}
+//!     })
+//! );
+//! assert_eq!(
+//!     json["localizations"],
+//!     json!({
+//!         "ru": {
+//!             "name/full": "Иван Петров",
+//!             "addresses/1": {
+//!                 "components": [
+//!                     {"kind": "name", "value": "Амир Темур 4"},
+//!                     {"kind": "locality", "value": "Ташкент"},
+//!                     {"kind": "postcode", "value": "100000"},
+//!                     {"kind": "country", "value": "Узбекистан"}
+//!                 ],
+//!                 "countryCode": "UZ",
+//!                 "contexts": {"work": true}
+//!             }
+//!         }
+//!     })
+//! );
+//!
+//! let converted = Vcard::from(&card);
+//! assert_eq!(Card::from(&converted), card);
+//!
+//! // The same properties, up to their order and ALTID values
+//! let without_altid = |vcard: &Vcard| {
+//!     let mut properties: Vec<_> = vcard
+//!         .properties
+//!         .iter()
+//!         .cloned()
+//!         .map(|mut property| {
+//!             property.parameters.shift_remove("altid");
+//!             property.parameters.sort_keys();
+//!             serde_json::to_string(&property).unwrap()
+//!         })
+//!         .collect();
+//!     properties.sort();
+//!     properties
+//! };
+//! assert_eq!(without_altid(&converted), without_altid(&vcard));
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Parameters, Property};
+
+pub use convert::generated_uid;
+mod convert;
+
+/// An identifier of an object in a [`Card`] map, such as `"1"` in `{"phones": {"1": {...}}}`.
+pub type Id = String;
+
+/// The contexts in which an object is used, e.g. `{"work": true}`.
+pub type Contexts = BTreeMap<String, bool>;
+
+/// Patches to apply to a [`Card`] for a language, keyed by the JSON pointer of the patched member.
+pub type PatchObject = BTreeMap<String, Value>;
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// A JSContact card ([RFC 9553, Section 2](https://datatracker.ietf.org/doc/html/rfc9553#section-2)).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "Card", rename_all = "camelCase")]
+pub struct Card {
+    pub version: String,
+    /// Generated with [`generated_uid`] if the source vCard has no `UID` property.
+    pub uid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prod_id: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub members: BTreeMap<String, bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub related_to: BTreeMap<String, Relation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<Name>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub nicknames: BTreeMap<Id, Nickname>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub organizations: BTreeMap<Id, Organization>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speak_to_as: Option<SpeakToAs>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub titles: BTreeMap<Id, Title>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub emails: BTreeMap<Id, EmailAddress>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub online_services: BTreeMap<Id, OnlineService>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub phones: BTreeMap<Id, Phone>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub preferred_languages: BTreeMap<Id, LanguagePref>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub calendars: BTreeMap<Id, Calendar>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scheduling_addresses: BTreeMap<Id, SchedulingAddress>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub addresses: BTreeMap<Id, Address>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub crypto_keys: BTreeMap<Id, CryptoKey>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub directories: BTreeMap<Id, Directory>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<Id, Link>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub media: BTreeMap<Id, Media>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub localizations: BTreeMap<String, PatchObject>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub anniversaries: BTreeMap<Id, Anniversary>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keywords: BTreeMap<String, bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub notes: BTreeMap<Id, Note>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub personal_info: BTreeMap<Id, PersonalInfo>,
+    /// vCard properties without a JSContact equivalent
+    /// ([RFC 9555, Section 3.3.1](https://datatracker.ietf.org/doc/html/rfc9555#section-3.3.1)).
+    #[serde(rename = "vCardProps", default, skip_serializing_if = "Vec::is_empty")]
+    pub v_card_props: Vec<Property>,
+}
+
+impl Default for Card {
+    fn default() -> Self {
+        Self {
+            version: "1.0".into(),
+            uid: String::new(),
+            kind: None,
+            created: None,
+            updated: None,
+            language: None,
+            prod_id: None,
+            members: BTreeMap::new(),
+            related_to: BTreeMap::new(),
+            name: None,
+            nicknames: BTreeMap::new(),
+            organizations: BTreeMap::new(),
+            speak_to_as: None,
+            titles: BTreeMap::new(),
+            emails: BTreeMap::new(),
+            online_services: BTreeMap::new(),
+            phones: BTreeMap::new(),
+            preferred_languages: BTreeMap::new(),
+            calendars: BTreeMap::new(),
+            scheduling_addresses: BTreeMap::new(),
+            addresses: BTreeMap::new(),
+            crypto_keys: BTreeMap::new(),
+            directories: BTreeMap::new(),
+            links: BTreeMap::new(),
+            media: BTreeMap::new(),
+            localizations: BTreeMap::new(),
+            anniversaries: BTreeMap::new(),
+            keywords: BTreeMap::new(),
+            notes: BTreeMap::new(),
+            personal_info: BTreeMap::new(),
+            v_card_props: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Relation {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub relation: BTreeMap<String, bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Name {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<NameComponent>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub is_ordered: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_separator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sort_as: BTreeMap<String, String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NameComponent {
+    /// E.g. `"given"` or `"surname"`.
+    pub kind: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phonetic: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Nickname {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Organization {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub units: Vec<OrgUnit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_as: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrgUnit {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_as: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeakToAs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grammatical_gender: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pronouns: BTreeMap<Id, Pronouns>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pronouns {
+    pub pronouns: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Title {
+    pub name: String,
+    /// Either `"title"` or `"role"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<Id>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailAddress {
+    pub address: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnlineService {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The vCard property the service was converted from, e.g. `"impp"`.
+    #[serde(rename = "vCardName", default, skip_serializing_if = "Option::is_none")]
+    pub v_card_name: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Phone {
+    pub number: String,
+    /// E.g. `{"voice": true, "mobile": true}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub features: BTreeMap<String, bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguagePref {
+    pub language: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+/// A calendar (`CALURI`) or free/busy (`FBURL`) resource.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Calendar {
+    /// Either `"calendar"` or `"freeBusy"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulingAddress {
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+/// A postal address. Not to be confused with the [`crate::Address`] helper for `adr` properties.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<AddressComponent>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub is_ordered: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_separator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressComponent {
+    /// E.g. `"locality"` or `"postcode"`.
+    pub kind: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phonetic: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CryptoKey {
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+/// A directory (`ORG-DIRECTORY`) or directory entry (`SOURCE`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Directory {
+    /// Either `"directory"` or `"entry"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_as: Option<u32>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    /// `"contact"` for a contact URI, otherwise absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Media {
+    /// One of `"photo"`, `"sound"` or `"logo"`.
+    pub kind: String,
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: Contexts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anniversary {
+    /// One of `"birth"`, `"death"` or `"wedding"`.
+    pub kind: String,
+    pub date: AnniversaryDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub place: Option<Address>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AnniversaryDate {
+    Timestamp(Timestamp),
+    PartialDate(PartialDate),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "Timestamp")]
+pub struct Timestamp {
+    /// A UTC date-time such as `"2010-10-10T10:10:10Z"`.
+    pub utc: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialDate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub month: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar_scale: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub note: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<Author>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Author {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+}
+
+/// Expertise, hobbies and interests.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonalInfo {
+    /// One of `"expertise"`, `"hobby"` or `"interest"`.
+    pub kind: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_as: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "vCardParams",
+        default,
+        skip_serializing_if = "Parameters::is_empty"
+    )]
+    pub v_card_params: Parameters,
+}
@@ -0,0 +1,1389 @@
+//! [RFC 9555](https://datatracker.ietf.org/doc/html/rfc9555) mapping between [`Vcard`] and [`Card`].
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::*;
+use crate::{
+    datetime::{Date, DateAndOrTime, Zone},
+    PropertyName, PropertyValue, ValueType, Vcard,
+};
+
+/// `N` components in jCard order.
+const NAME_KINDS: [&str; 7] = [
+    "surname",
+    "given",
+    "given2",
+    "title",
+    "credential",
+    "surname2",
+    "generation",
+];
+
+/// `ADR` components in jCard order.
+const ADDRESS_KINDS: [&str; 7] = [
+    "postOfficeBox",
+    "apartment",
+    "name",
+    "locality",
+    "region",
+    "postcode",
+    "country",
+];
+
+impl From<&Vcard> for Card {
+    /// Converts a vCard to a JSContact card.
+    ///
+    /// The most preferred `FN` becomes `name/full`. Its `LANGUAGE` becomes the language of the card if the vCard has
+    /// no `LANGUAGE` property, or a localization of `name/full` if the card is in another language. A vCard without a
+    /// `UID` gets a [`generated_uid`].
+    fn from(vcard: &Vcard) -> Self {
+        let mut card = Card::default();
+        let localized = localized_members(&vcard.properties);
+        let mut paths: HashMap<usize, Vec<String>> = HashMap::new();
+
+        // The most preferred full name is mapped first, so it becomes `name/full`
+        let full_name = vcard
+            .properties
+            .iter()
+            .enumerate()
+            .filter(|(index, property)| {
+                property.property_name() == PropertyName::Fn && !localized.contains_key(index)
+            })
+            .min_by_key(|(_, property)| property.parameters.pref().unwrap_or(u8::MAX))
+            .map(|(index, _)| index);
+        let order = full_name
+            .into_iter()
+            .chain((0..vcard.properties.len()).filter(|index| Some(*index) != full_name));
+
+        for index in order {
+            let property = &vcard.properties[index];
+            if localized.contains_key(&index) {
+                continue;
+            }
+
+            let id = property
+                .parameters
+                .get("prop-id")
+                .and_then(|ids| ids.first())
+                .map(String::as_str);
+
+            let inserted = map_property(property).and_then(|mapped| {
+                mapped
+                    .into_iter()
+                    .map(|mapped| insert(&mut card, mapped, id))
+                    .collect::<Option<Vec<_>>>()
+            });
+
+            match inserted {
+                Some(inserted) => {
+                    paths.insert(index, inserted);
+                }
+                None => card.v_card_props.push(property.clone()),
+            }
+        }
+
+        for (index, (base, language)) in localized {
+            let property = &vcard.properties[index];
+            let mut stripped = property.clone();
+            stripped.parameters.shift_remove("language");
+
+            let patches = paths
+                .get(&base)
+                .zip(map_property(&stripped))
+                .filter(|(paths, mapped)| paths.len() == mapped.len());
+
+            match patches {
+                Some((paths, mapped)) => {
+                    let patch = card.localizations.entry(language).or_default();
+                    for (path, mapped) in paths.iter().zip(mapped) {
+                        patch.insert(path.clone(), mapped.patch());
+                    }
+                }
+                None => card.v_card_props.push(property.clone()),
+            }
+        }
+
+        let full_name = full_name
+            .filter(|index| {
+                paths
+                    .get(index)
+                    .is_some_and(|paths| paths == &["name/full"])
+            })
+            .map(|index| &vcard.properties[index]);
+        if let Some((language, full)) = full_name.and_then(|property| {
+            let language = property.parameters.language()?;
+            Some((language.to_string(), text(property)?))
+        }) {
+            match &card.language {
+                None => card.language = Some(language),
+                Some(card_language) if *card_language == language => {}
+                Some(_) => {
+                    let patch = card.localizations.entry(language).or_default();
+                    patch
+                        .entry("name/full".into())
+                        .or_insert(Value::String(full));
+                }
+            }
+        }
+
+        if card.uid.is_empty() {
+            card.uid = generated_uid(vcard);
+        }
+
+        card
+    }
+}
+
+/// A `urn:uuid:` UID for a vCard without one, derived from its jCard so that converting the same vCard twice gives the
+/// same card.
+///
+/// The UUID is a version 8 UUID ([RFC 9562](https://datatracker.ietf.org/doc/html/rfc9562#section-5.8)) holding the
+/// 128-bit FNV-1a hash of the jCard.
+pub fn generated_uid(vcard: &Vcard) -> String {
+    const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    let jcard = serde_json::to_vec(vcard).unwrap_or_default();
+    let hash = jcard.iter().fold(OFFSET, |hash, byte| {
+        (hash ^ u128::from(*byte)).wrapping_mul(PRIME)
+    });
+    // Version 8 and the RFC 9562 variant
+    let uuid = (hash & !(0xf << 76) & !(0b11 << 62)) | (0x8 << 76) | (0b10 << 62);
+
+    let hex = format!("{uuid:032x}");
+    format!(
+        "urn:uuid:{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+impl From<&Card> for Vcard {
+    /// Converts a JSContact card back to a vCard 4.0.
+    ///
+    /// The `label` members of the card objects have no vCard equivalent and are not converted.
+    fn from(card: &Card) -> Self {
+        let mut altids = HashMap::new();
+        for path in card.localizations.values().flat_map(BTreeMap::keys) {
+            let next = (altids.len() + 1).to_string();
+            altids.entry(path.clone()).or_insert(next);
+        }
+
+        let mut vcard = Vcard::default();
+        let mut push = |path: String, mut property: Property| {
+            if let Some(altid) = altids.get(&path) {
+                property
+                    .parameters
+                    .insert("altid".into(), vec![altid.clone()]);
+            }
+            vcard.push(property);
+        };
+
+        if !card.uid.is_empty() {
+            let value_type = if card.uid.contains(':') {
+                ValueType::Uri
+            } else {
+                ValueType::Text
+            };
+            push(
+                "uid".into(),
+                Property::new("uid", None, value_type, &card.uid),
+            );
+        }
+        if let Some(kind) = &card.kind {
+            push(
+                "kind".into(),
+                Property::new("kind", None, ValueType::Text, kind),
+            );
+        }
+
+        if let Some(name) = &card.name {
+            if let Some(full) = &name.full {
+                push("name/full".into(), Property::new_fn(full, None));
+            }
+            if let Some(property) = name_property(name) {
+                push("name/components".into(), property);
+            }
+        }
+
+        for (id, nickname) in &card.nicknames {
+            push(format!("nicknames/{id}"), nickname_property(nickname));
+        }
+        for (id, organization) in &card.organizations {
+            push(
+                format!("organizations/{id}"),
+                organization_property(organization),
+            );
+        }
+
+        if let Some(speak_to_as) = &card.speak_to_as {
+            if let Some(gender) = &speak_to_as.grammatical_gender {
+                let property = Property::new("gramgender", None, ValueType::Text, gender);
+                push("speakToAs/grammaticalGender".into(), property);
+            }
+            for (id, pronouns) in &speak_to_as.pronouns {
+                push(
+                    format!("speakToAs/pronouns/{id}"),
+                    pronouns_property(pronouns),
+                );
+            }
+        }
+
+        for (id, title) in &card.titles {
+            push(format!("titles/{id}"), title_property(title));
+        }
+        for (id, email) in &card.emails {
+            push(format!("emails/{id}"), email_property(email));
+        }
+        for (id, service) in &card.online_services {
+            if let Some(property) = online_service_property(service) {
+                push(format!("onlineServices/{id}"), property);
+            }
+        }
+        for (id, phone) in &card.phones {
+            push(format!("phones/{id}"), phone_property(phone));
+        }
+        for (id, language) in &card.preferred_languages {
+            push(
+                format!("preferredLanguages/{id}"),
+                language_property(language),
+            );
+        }
+        for (id, calendar) in &card.calendars {
+            push(format!("calendars/{id}"), calendar_property(calendar));
+        }
+        for (id, address) in &card.scheduling_addresses {
+            push(
+                format!("schedulingAddresses/{id}"),
+                scheduling_property(address),
+            );
+        }
+        for (id, address) in &card.addresses {
+            push(format!("addresses/{id}"), address_property(address));
+        }
+        for (id, key) in &card.crypto_keys {
+            push(format!("cryptoKeys/{id}"), crypto_key_property(key));
+        }
+        for (id, directory) in &card.directories {
+            push(format!("directories/{id}"), directory_property(directory));
+        }
+        for (id, link) in &card.links {
+            push(format!("links/{id}"), link_property(link));
+        }
+        for (id, media) in &card.media {
+            push(format!("media/{id}"), media_property(media));
+        }
+
+        for (id, anniversary) in &card.anniversaries {
+            for property in anniversary_properties(anniversary) {
+                push(format!("anniversaries/{id}"), property);
+            }
+        }
+
+        let keywords: Vec<PropertyValue> = card
+            .keywords
+            .iter()
+            .filter(|(_, enabled)| **enabled)
+            .map(|(keyword, _)| keyword.into())
+            .collect();
+        if !keywords.is_empty() {
+            let property = Property::new_multivalued("categories", None, ValueType::Text, keywords);
+            push("keywords".into(), property);
+        }
+
+        for (id, note) in &card.notes {
+            push(format!("notes/{id}"), note_property(note));
+        }
+        for (id, info) in &card.personal_info {
+            push(format!("personalInfo/{id}"), personal_info_property(info));
+        }
+
+        for (uri, relation) in &card.related_to {
+            let value_type = if uri.contains(':') {
+                ValueType::Uri
+            } else {
+                ValueType::Text
+            };
+            let mut parameters = Parameters::new();
+            add_types(&mut parameters, relation.relation.iter(), |relation| {
+                relation
+            });
+            push(
+                format!("relatedTo/{}", pointer(uri)),
+                Property::new("related", parameters, value_type, uri),
+            );
+        }
+        for (uri, _) in card.members.iter().filter(|(_, member)| **member) {
+            push(
+                format!("members/{}", pointer(uri)),
+                Property::new("member", None, ValueType::Uri, uri),
+            );
+        }
+
+        if let Some(language) = &card.language {
+            push(
+                "language".into(),
+                Property::new("language", None, ValueType::LanguageTag, language),
+            );
+        }
+        if let Some(prod_id) = &card.prod_id {
+            push(
+                "prodId".into(),
+                Property::new("prodid", None, ValueType::Text, prod_id),
+            );
+        }
+        if let Some(created) = &card.created {
+            push(
+                "created".into(),
+                Property::new("created", None, ValueType::Timestamp, created),
+            );
+        }
+        if let Some(updated) = &card.updated {
+            push(
+                "updated".into(),
+                Property::new("rev", None, ValueType::Timestamp, updated),
+            );
+        }
+
+        for (language, patch) in &card.localizations {
+            for (path, value) in patch {
+                if let Some(mut property) = localized_property(path, value.clone()) {
+                    property
+                        .parameters
+                        .insert("language".into(), vec![language.clone()]);
+                    push(path.clone(), property);
+                }
+            }
+        }
+
+        for property in &card.v_card_props {
+            push(String::new(), property.clone());
+        }
+
+        vcard
+    }
+}
+
+/// A card member produced from a single vCard property (or a single value of a multi-valued property).
+enum Mapped {
+    Uid(String),
+    Kind(String),
+    Created(String),
+    Updated(String),
+    Language(String),
+    ProdId(String),
+    Member(String),
+    Related(String, Relation),
+    Full(String),
+    Name(Name),
+    Nickname(Nickname),
+    Organization(Organization),
+    GrammaticalGender(String),
+    Pronouns(Pronouns),
+    Title(Title),
+    Email(EmailAddress),
+    OnlineService(OnlineService),
+    Phone(Phone),
+    LanguagePref(LanguagePref),
+    Calendar(Calendar),
+    SchedulingAddress(SchedulingAddress),
+    Address(Address),
+    CryptoKey(CryptoKey),
+    Directory(Directory),
+    Link(Link),
+    Media(Media),
+    Anniversary(Anniversary),
+    Keyword(String),
+    Note(Note),
+    PersonalInfo(PersonalInfo),
+}
+
+impl Mapped {
+    /// The value of the localization patch replacing this member.
+    fn patch(self) -> Value {
+        fn json(value: impl Serialize) -> Value {
+            serde_json::to_value(value).unwrap_or_default()
+        }
+
+        match self {
+            Mapped::Uid(text)
+            | Mapped::Kind(text)
+            | Mapped::Created(text)
+            | Mapped::Updated(text)
+            | Mapped::Language(text)
+            | Mapped::ProdId(text)
+            | Mapped::Full(text)
+            | Mapped::GrammaticalGender(text) => Value::String(text),
+            Mapped::Member(_) | Mapped::Keyword(_) => Value::Bool(true),
+            Mapped::Related(_, relation) => json(relation),
+            Mapped::Name(name) => json(name.components),
+            Mapped::Nickname(nickname) => json(nickname),
+            Mapped::Organization(organization) => json(organization),
+            Mapped::Pronouns(pronouns) => json(pronouns),
+            Mapped::Title(title) => json(title),
+            Mapped::Email(email) => json(email),
+            Mapped::OnlineService(service) => json(service),
+            Mapped::Phone(phone) => json(phone),
+            Mapped::LanguagePref(language) => json(language),
+            Mapped::Calendar(calendar) => json(calendar),
+            Mapped::SchedulingAddress(address) => json(address),
+            Mapped::Address(address) => json(address),
+            Mapped::CryptoKey(key) => json(key),
+            Mapped::Directory(directory) => json(directory),
+            Mapped::Link(link) => json(link),
+            Mapped::Media(media) => json(media),
+            Mapped::Anniversary(anniversary) => json(anniversary),
+            Mapped::Note(note) => json(note),
+            Mapped::PersonalInfo(info) => json(info),
+        }
+    }
+}
+
+/// Finds the properties that localize another property with the same `ALTID`, mapping their index to the index of
+/// the localized property and their language.
+///
+/// The localized property is the first one in the `ALTID` group without a `LANGUAGE` parameter, or the first one in
+/// the group if all of them have a language.
+fn localized_members(properties: &[Property]) -> BTreeMap<usize, (usize, String)> {
+    let mut groups: HashMap<(String, &str), Vec<usize>> = HashMap::new();
+    for (index, property) in properties.iter().enumerate() {
+        if let Some(altid) = first(&property.parameters, "altid") {
+            let key = (property.name.to_lowercase(), altid);
+            groups.entry(key).or_default().push(index);
+        }
+    }
+
+    let mut localized = BTreeMap::new();
+    for group in groups.values() {
+        let base = group
+            .iter()
+            .copied()
+            .find(|index| !properties[*index].parameters.contains_key("language"))
+            .unwrap_or(group[0]);
+
+        for &index in group.iter().filter(|index| **index != base) {
+            if let Some(language) = first(&properties[index].parameters, "language") {
+                localized.insert(index, (base, language.to_string()));
+            }
+        }
+    }
+
+    localized
+}
+
+/// Adds a mapped member to the card, returning its JSON pointer or [`None`] if the card already has that member.
+fn insert(card: &mut Card, mapped: Mapped, id: Option<&str>) -> Option<String> {
+    fn entry<T>(map: &mut BTreeMap<Id, T>, collection: &str, id: Option<&str>, value: T) -> String {
+        let id = match id {
+            Some(id) if !map.contains_key(id) => id.to_string(),
+            _ => (1..)
+                .map(|n: usize| n.to_string())
+                .find(|n| !map.contains_key(n))
+                .unwrap_or_default(),
+        };
+        map.insert(id.clone(), value);
+        format!("{collection}/{id}")
+    }
+
+    fn single(slot: &mut Option<String>, path: &str, value: String) -> Option<String> {
+        if slot.is_some() {
+            return None;
+        }
+        *slot = Some(value);
+        Some(path.into())
+    }
+
+    let path = match mapped {
+        Mapped::Uid(uid) => {
+            if !card.uid.is_empty() {
+                return None;
+            }
+            card.uid = uid;
+            "uid".into()
+        }
+        Mapped::Kind(kind) => single(&mut card.kind, "kind", kind)?,
+        Mapped::Created(created) => single(&mut card.created, "created", created)?,
+        Mapped::Updated(updated) => single(&mut card.updated, "updated", updated)?,
+        Mapped::Language(language) => single(&mut card.language, "language", language)?,
+        Mapped::ProdId(prod_id) => single(&mut card.prod_id, "prodId", prod_id)?,
+        Mapped::Member(uri) => {
+            let path = format!("members/{}", pointer(&uri));
+            card.members.insert(uri, true);
+            path
+        }
+        Mapped::Related(uri, relation) => {
+            let path = format!("relatedTo/{}", pointer(&uri));
+            card.related_to.insert(uri, relation);
+            path
+        }
+        Mapped::Full(full) => {
+            let name = card.name.get_or_insert_with(Name::default);
+            single(&mut name.full, "name/full", full)?
+        }
+        Mapped::Name(components) => {
+            let name = card.name.get_or_insert_with(Name::default);
+            if !name.components.is_empty() || !name.v_card_params.is_empty() {
+                return None;
+            }
+            name.components = components.components;
+            name.sort_as = components.sort_as;
+            name.v_card_params = components.v_card_params;
+            "name/components".into()
+        }
+        Mapped::GrammaticalGender(gender) => {
+            let speak_to_as = card.speak_to_as.get_or_insert_with(SpeakToAs::default);
+            let path = "speakToAs/grammaticalGender";
+            single(&mut speak_to_as.grammatical_gender, path, gender)?
+        }
+        Mapped::Pronouns(pronouns) => {
+            let speak_to_as = card.speak_to_as.get_or_insert_with(SpeakToAs::default);
+            entry(
+                &mut speak_to_as.pronouns,
+                "speakToAs/pronouns",
+                id,
+                pronouns,
+            )
+        }
+        Mapped::Keyword(keyword) => {
+            let path = format!("keywords/{}", pointer(&keyword));
+            card.keywords.insert(keyword, true);
+            path
+        }
+        Mapped::Nickname(value) => entry(&mut card.nicknames, "nicknames", id, value),
+        Mapped::Organization(value) => entry(&mut card.organizations, "organizations", id, value),
+        Mapped::Title(value) => entry(&mut card.titles, "titles", id, value),
+        Mapped::Email(value) => entry(&mut card.emails, "emails", id, value),
+        Mapped::OnlineService(value) => {
+            entry(&mut card.online_services, "onlineServices", id, value)
+        }
+        Mapped::Phone(value) => entry(&mut card.phones, "phones", id, value),
+        Mapped::LanguagePref(value) => entry(
+            &mut card.preferred_languages,
+            "preferredLanguages",
+            id,
+            value,
+        ),
+        Mapped::Calendar(value) => entry(&mut card.calendars, "calendars", id, value),
+        Mapped::SchedulingAddress(value) => entry(
+            &mut card.scheduling_addresses,
+            "schedulingAddresses",
+            id,
+            value,
+        ),
+        Mapped::Address(value) => entry(&mut card.addresses, "addresses", id, value),
+        Mapped::CryptoKey(value) => entry(&mut card.crypto_keys, "cryptoKeys", id, value),
+        Mapped::Directory(value) => entry(&mut card.directories, "directories", id, value),
+        Mapped::Link(value) => entry(&mut card.links, "links", id, value),
+        Mapped::Media(value) => entry(&mut card.media, "media", id, value),
+        Mapped::Anniversary(value) => entry(&mut card.anniversaries, "anniversaries", id, value),
+        Mapped::Note(value) => entry(&mut card.notes, "notes", id, value),
+        Mapped::PersonalInfo(value) => entry(&mut card.personal_info, "personalInfo", id, value),
+    };
+
+    Some(path)
+}
+
+/// Escapes a map key for use in a JSON pointer.
+fn pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// The parameters of a property that are yet to be mapped to a JSContact member.
+struct Params(Parameters);
+
+impl Params {
+    fn new(property: &Property) -> Self {
+        let mut parameters = property.parameters.clone();
+        parameters.shift_remove("altid");
+        parameters.shift_remove("prop-id");
+        Self(parameters)
+    }
+
+    fn one(&mut self, key: &str) -> Option<String> {
+        self.0.shift_remove(key).map(|values| values.join(","))
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, key: &str) -> Option<T> {
+        let number = first(&self.0, key)?.parse().ok()?;
+        self.0.shift_remove(key);
+        Some(number)
+    }
+
+    fn pref(&mut self) -> Option<u8> {
+        let pref = self.0.pref()?;
+        self.0.shift_remove("pref");
+        Some(pref)
+    }
+
+    /// Removes the `TYPE` values accepted by `map`, returning them as a JSContact boolean map.
+    fn types(&mut self, map: impl Fn(&str) -> Option<String>) -> BTreeMap<String, bool> {
+        let mut mapped = BTreeMap::new();
+        let Some(types) = self.0.get_mut("type") else {
+            return mapped;
+        };
+
+        types.retain(|value| match map(&value.to_lowercase()) {
+            Some(value) => {
+                mapped.insert(value, true);
+                false
+            }
+            None => true,
+        });
+        if types.is_empty() {
+            self.0.shift_remove("type");
+        }
+
+        mapped
+    }
+
+    fn contexts(&mut self) -> Contexts {
+        self.types(|value| match value {
+            "work" => Some("work".into()),
+            "home" => Some("private".into()),
+            _ => None,
+        })
+    }
+
+    fn finish(self) -> Parameters {
+        self.0
+    }
+}
+
+fn first<'a>(parameters: &'a Parameters, key: &str) -> Option<&'a str> {
+    parameters.get(key)?.first().map(String::as_str)
+}
+
+fn text(property: &Property) -> Option<String> {
+    match property.values.as_slice() {
+        [PropertyValue::String(text)] => Some(text.clone()),
+        _ => None,
+    }
+}
+
+fn uri(property: &Property) -> Option<String> {
+    (property.value_type == ValueType::Uri).then(|| text(property))?
+}
+
+fn texts(property: &Property) -> Option<Vec<String>> {
+    property
+        .values
+        .iter()
+        .map(|value| match value {
+            PropertyValue::String(text) => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The components of a structured property, each with its list of values.
+fn components(property: &Property, max: usize) -> Option<Vec<Vec<String>>> {
+    let values = match property.values.as_slice() {
+        [PropertyValue::Structured(components)] => components.as_slice(),
+        values @ [PropertyValue::String(_)] => values,
+        _ => return None,
+    };
+
+    if values.len() > max {
+        return None;
+    }
+
+    values
+        .iter()
+        .map(|component| match component {
+            PropertyValue::String(text) if text.is_empty() => Some(Vec::new()),
+            PropertyValue::String(text) => Some(vec![text.clone()]),
+            PropertyValue::Structured(list) => list
+                .iter()
+                .map(|value| match value {
+                    PropertyValue::String(text) => Some(text.clone()),
+                    _ => None,
+                })
+                .filter(|value| value.as_deref() != Some(""))
+                .collect(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Maps a vCard property to JSContact members, or returns [`None`] if it must be kept in `vCardProps`.
+fn map_property(property: &Property) -> Option<Vec<Mapped>> {
+    let mut params = Params::new(property);
+    let name = property.name.to_lowercase();
+
+    let mapped = match name.as_str() {
+        "fn" => {
+            // The language and preference of the full name are handled by the caller
+            params.0.shift_remove("language");
+            params.pref();
+            if !params.0.is_empty() {
+                return None;
+            }
+            Mapped::Full(text(property)?)
+        }
+        "uid" | "kind" | "created" | "rev" | "language" | "prodid" | "member" | "gramgender"
+        | "categories"
+            if !params.0.is_empty() =>
+        {
+            return None
+        }
+        "uid" => Mapped::Uid(text(property)?),
+        "kind" => Mapped::Kind(text(property)?.to_lowercase()),
+        "created" => Mapped::Created(text(property)?),
+        "rev" => Mapped::Updated(text(property)?),
+        "language" => Mapped::Language(text(property)?),
+        "prodid" => Mapped::ProdId(text(property)?),
+        "member" => Mapped::Member(text(property)?),
+        "gramgender" => Mapped::GrammaticalGender(text(property)?.to_lowercase()),
+        "categories" => return Some(texts(property)?.into_iter().map(Mapped::Keyword).collect()),
+        "related" => {
+            let relation = params.types(|value| Some(value.into()));
+            if !params.0.is_empty() {
+                return None;
+            }
+            Mapped::Related(text(property)?, Relation { relation })
+        }
+        "n" => {
+            let components = components(property, NAME_KINDS.len())?;
+            let sort_as = params.0.shift_remove("sort-as").unwrap_or_default();
+
+            Mapped::Name(Name {
+                components: components
+                    .into_iter()
+                    .zip(NAME_KINDS)
+                    .flat_map(|(values, kind)| {
+                        values.into_iter().map(move |value| NameComponent {
+                            kind: kind.into(),
+                            value,
+                            phonetic: None,
+                        })
+                    })
+                    .collect(),
+                sort_as: sort_as
+                    .into_iter()
+                    .zip(NAME_KINDS)
+                    .filter(|(value, _)| !value.is_empty())
+                    .map(|(value, kind)| (kind.into(), value))
+                    .collect(),
+                v_card_params: params.finish(),
+                ..Default::default()
+            })
+        }
+        "nickname" => {
+            let contexts = params.contexts();
+            let pref = params.pref();
+            let v_card_params = params.finish();
+
+            return Some(
+                texts(property)?
+                    .into_iter()
+                    .map(|name| {
+                        Mapped::Nickname(Nickname {
+                            name,
+                            contexts: contexts.clone(),
+                            pref,
+                            v_card_params: v_card_params.clone(),
+                        })
+                    })
+                    .collect(),
+            );
+        }
+        "org" => {
+            let mut names = components(property, usize::MAX)?
+                .into_iter()
+                .map(|values| values.join(","));
+
+            Mapped::Organization(Organization {
+                name: names.next().filter(|name| !name.is_empty()),
+                units: names
+                    .map(|name| OrgUnit {
+                        name,
+                        sort_as: None,
+                    })
+                    .collect(),
+                sort_as: params.one("sort-as"),
+                contexts: params.contexts(),
+                v_card_params: params.finish(),
+            })
+        }
+        "title" | "role" => Mapped::Title(Title {
+            name: text(property)?,
+            kind: Some(name.clone()),
+            organization_id: None,
+            v_card_params: params.finish(),
+        }),
+        "email" => Mapped::Email(EmailAddress {
+            address: text(property)?,
+            contexts: params.contexts(),
+            pref: params.pref(),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "impp" | "socialprofile" => {
+            let (uri, user) = match uri(property) {
+                Some(uri) => (Some(uri), None),
+                None if name == "socialprofile" => (None, Some(text(property)?)),
+                None => return None,
+            };
+
+            Mapped::OnlineService(OnlineService {
+                service: params.one("service-type"),
+                uri,
+                user,
+                contexts: params.contexts(),
+                pref: params.pref(),
+                label: None,
+                v_card_name: (name == "impp").then(|| name.clone()),
+                v_card_params: params.finish(),
+            })
+        }
+        "tel" => Mapped::Phone(Phone {
+            number: text(property)?,
+            contexts: params.contexts(),
+            features: params.types(|value| match value {
+                "voice" | "fax" | "video" | "pager" | "textphone" | "text" => Some(value.into()),
+                "cell" => Some("mobile".into()),
+                "main-number" => Some("mainNumber".into()),
+                _ => None,
+            }),
+            pref: params.pref(),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "lang" => Mapped::LanguagePref(LanguagePref {
+            language: text(property)?,
+            contexts: params.contexts(),
+            pref: params.pref(),
+            v_card_params: params.finish(),
+        }),
+        "caluri" | "fburl" => Mapped::Calendar(Calendar {
+            kind: Some(
+                if name == "caluri" {
+                    "calendar"
+                } else {
+                    "freeBusy"
+                }
+                .into(),
+            ),
+            uri: uri(property)?,
+            media_type: params.one("mediatype"),
+            contexts: params.contexts(),
+            pref: params.pref(),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "caladruri" => Mapped::SchedulingAddress(SchedulingAddress {
+            uri: uri(property)?,
+            contexts: params.contexts(),
+            pref: params.pref(),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "adr" => {
+            let components = components(property, ADDRESS_KINDS.len())?;
+
+            Mapped::Address(Address {
+                components: components
+                    .into_iter()
+                    .zip(ADDRESS_KINDS)
+                    .flat_map(|(values, kind)| {
+                        values.into_iter().map(move |value| AddressComponent {
+                            kind: kind.into(),
+                            value,
+                            phonetic: None,
+                        })
+                    })
+                    .collect(),
+                full: params.one("label"),
+                country_code: params.one("cc"),
+                coordinates: params.one("geo"),
+                time_zone: params.one("tz"),
+                contexts: params.types(|value| match value {
+                    "work" => Some("work".into()),
+                    "home" => Some("private".into()),
+                    "billing" | "delivery" => Some(value.into()),
+                    _ => None,
+                }),
+                pref: params.pref(),
+                v_card_params: params.finish(),
+                ..Default::default()
+            })
+        }
+        "key" => Mapped::CryptoKey(CryptoKey {
+            uri: uri(property)?,
+            media_type: params.one("mediatype"),
+            contexts: params.contexts(),
+            pref: params.pref(),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "source" | "org-directory" => Mapped::Directory(Directory {
+            kind: Some(
+                if name == "source" {
+                    "entry"
+                } else {
+                    "directory"
+                }
+                .into(),
+            ),
+            uri: uri(property)?,
+            media_type: params.one("mediatype"),
+            list_as: params.number("index"),
+            contexts: params.contexts(),
+            pref: params.pref(),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "url" | "contact-uri" => Mapped::Link(Link {
+            kind: (name == "contact-uri").then(|| "contact".into()),
+            uri: uri(property)?,
+            media_type: params.one("mediatype"),
+            contexts: params.contexts(),
+            pref: params.pref(),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "photo" | "logo" | "sound" => Mapped::Media(Media {
+            kind: name.clone(),
+            uri: uri(property)?,
+            media_type: params.one("mediatype"),
+            contexts: params.contexts(),
+            pref: params.pref(),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "bday" | "anniversary" | "deathdate" => {
+            let date = match anniversary_date(property)? {
+                AnniversaryDate::PartialDate(date) => AnniversaryDate::PartialDate(PartialDate {
+                    calendar_scale: params.one("calscale"),
+                    ..date
+                }),
+                timestamp => timestamp,
+            };
+
+            Mapped::Anniversary(Anniversary {
+                kind: match name.as_str() {
+                    "bday" => "birth",
+                    "deathdate" => "death",
+                    _ => "wedding",
+                }
+                .into(),
+                date,
+                place: None,
+                v_card_params: params.finish(),
+            })
+        }
+        "note" => {
+            let author = Author {
+                name: params.one("author-name"),
+                uri: params.one("author"),
+            };
+
+            Mapped::Note(Note {
+                note: text(property)?,
+                created: params.one("created"),
+                author: (author.name.is_some() || author.uri.is_some()).then_some(author),
+                v_card_params: params.finish(),
+            })
+        }
+        "expertise" | "hobby" | "interest" => Mapped::PersonalInfo(PersonalInfo {
+            kind: name.clone(),
+            value: text(property)?,
+            level: params.one("level"),
+            list_as: params.number("index"),
+            label: None,
+            v_card_params: params.finish(),
+        }),
+        "pronouns" => Mapped::Pronouns(Pronouns {
+            pronouns: text(property)?,
+            contexts: params.contexts(),
+            pref: params.pref(),
+            v_card_params: params.finish(),
+        }),
+        _ => return None,
+    };
+
+    Some(vec![mapped])
+}
+
+/// The date of an anniversary, a date-time is only mapped if it is a complete UTC timestamp.
+fn anniversary_date(property: &Property) -> Option<AnniversaryDate> {
+    Some(match property.datetime()?.ok()? {
+        DateAndOrTime::Date(date) => AnniversaryDate::PartialDate(PartialDate {
+            year: date.year().map(u32::from),
+            month: date.month(),
+            day: date.day(),
+            calendar_scale: None,
+        }),
+        DateAndOrTime::DateTime(datetime)
+            if datetime.is_complete() && datetime.time().zone() == Some(Zone::Utc) =>
+        {
+            AnniversaryDate::Timestamp(Timestamp {
+                utc: datetime.to_string(),
+            })
+        }
+        _ => return None,
+    })
+}
+
+fn format_date(date: &PartialDate) -> Option<String> {
+    let year = date.year.map(u16::try_from).transpose().ok()?;
+    Date::new(year, date.month, date.day).map(|date| date.to_string())
+}
+
+/// Converts a localization patch back to a vCard property, for the members [`Mapped::patch`] produces.
+fn localized_property(path: &str, value: Value) -> Option<Property> {
+    fn from<T: serde::de::DeserializeOwned>(value: Value) -> Option<T> {
+        serde_json::from_value(value).ok()
+    }
+
+    let (collection, _) = path.split_once('/').unwrap_or((path, ""));
+    let property = match collection {
+        "name" if path == "name/full" => Property::new_fn(value.as_str()?, None),
+        "name" => name_property(&Name {
+            components: from(value)?,
+            ..Default::default()
+        })?,
+        "speakToAs" if path == "speakToAs/grammaticalGender" => {
+            Property::new("gramgender", None, ValueType::Text, value.as_str()?)
+        }
+        "speakToAs" => pronouns_property(&from(value)?),
+        "nicknames" => nickname_property(&from(value)?),
+        "organizations" => organization_property(&from(value)?),
+        "titles" => title_property(&from(value)?),
+        "emails" => email_property(&from(value)?),
+        "onlineServices" => online_service_property(&from(value)?)?,
+        "phones" => phone_property(&from(value)?),
+        "preferredLanguages" => language_property(&from(value)?),
+        "calendars" => calendar_property(&from(value)?),
+        "schedulingAddresses" => scheduling_property(&from(value)?),
+        "addresses" => address_property(&from(value)?),
+        "cryptoKeys" => crypto_key_property(&from(value)?),
+        "directories" => directory_property(&from(value)?),
+        "links" => link_property(&from(value)?),
+        "media" => media_property(&from(value)?),
+        "anniversaries" => anniversary_properties(&from(value)?).into_iter().next()?,
+        "notes" => note_property(&from(value)?),
+        "personalInfo" => personal_info_property(&from(value)?),
+        _ => return None,
+    };
+
+    Some(property)
+}
+
+/// The `TYPE` and `PREF` parameters of an object, on top of its `vCardParams`.
+fn parameters(v_card_params: &Parameters, contexts: &Contexts, pref: Option<u8>) -> Parameters {
+    let mut parameters = v_card_params.clone();
+    add_types(&mut parameters, contexts.iter(), |context| match context {
+        "private" => "home",
+        context => context,
+    });
+    if let Some(pref) = pref {
+        parameters.insert("pref".into(), vec![pref.to_string()]);
+    }
+    parameters
+}
+
+fn add_types<'a>(
+    parameters: &mut Parameters,
+    values: impl Iterator<Item = (&'a String, &'a bool)>,
+    map: impl Fn(&str) -> &str,
+) {
+    for (value, _) in values.filter(|(_, enabled)| **enabled) {
+        let types = parameters.entry("type".into()).or_default();
+        types.push(map(value).into());
+    }
+}
+
+fn set(parameters: &mut Parameters, key: &str, value: Option<impl ToString>) {
+    if let Some(value) = value {
+        parameters.insert(key.into(), vec![value.to_string()]);
+    }
+}
+
+/// A structured value from lists of component values, a component with several values becomes a nested array.
+fn structured(components: Vec<Vec<String>>) -> PropertyValue {
+    PropertyValue::Structured(
+        components
+            .into_iter()
+            .map(|mut values| match values.len() {
+                0 => PropertyValue::String(String::new()),
+                1 => PropertyValue::String(values.remove(0)),
+                _ => PropertyValue::Structured(values.into_iter().map(Into::into).collect()),
+            })
+            .collect(),
+    )
+}
+
+fn name_property(name: &Name) -> Option<Property> {
+    if name.components.is_empty() {
+        return None;
+    }
+
+    let mut components = vec![Vec::new(); NAME_KINDS.len()];
+    for component in &name.components {
+        if let Some(index) = NAME_KINDS.iter().position(|kind| *kind == component.kind) {
+            components[index].push(component.value.clone());
+        }
+    }
+    if components[5..].iter().all(Vec::is_empty) {
+        components.truncate(5);
+    }
+
+    let mut parameters = name.v_card_params.clone();
+    let sort_as: Vec<String> = NAME_KINDS
+        .iter()
+        .map(|kind| name.sort_as.get(*kind).cloned().unwrap_or_default())
+        .collect();
+    if let Some(last) = sort_as.iter().rposition(|value| !value.is_empty()) {
+        parameters.insert("sort-as".into(), sort_as[..=last].to_vec());
+    }
+
+    Some(Property::new(
+        "n",
+        parameters,
+        ValueType::Text,
+        structured(components),
+    ))
+}
+
+fn nickname_property(nickname: &Nickname) -> Property {
+    let parameters = parameters(&nickname.v_card_params, &nickname.contexts, nickname.pref);
+    Property::new("nickname", parameters, ValueType::Text, &nickname.name)
+}
+
+fn organization_property(organization: &Organization) -> Property {
+    let mut parameters = parameters(&organization.v_card_params, &organization.contexts, None);
+    set(&mut parameters, "sort-as", organization.sort_as.as_ref());
+
+    let name = organization.name.clone().unwrap_or_default();
+    let value = if organization.units.is_empty() {
+        name.into()
+    } else {
+        let units = organization
+            .units
+            .iter()
+            .map(|unit| unit.name.clone().into());
+        PropertyValue::Structured(std::iter::once(name.into()).chain(units).collect())
+    };
+
+    Property::new("org", parameters, ValueType::Text, value)
+}
+
+fn pronouns_property(pronouns: &Pronouns) -> Property {
+    let parameters = parameters(&pronouns.v_card_params, &pronouns.contexts, pronouns.pref);
+    Property::new("pronouns", parameters, ValueType::Text, &pronouns.pronouns)
+}
+
+fn title_property(title: &Title) -> Property {
+    let name = match title.kind.as_deref() {
+        Some("role") => "role",
+        _ => "title",
+    };
+    Property::new(
+        name,
+        title.v_card_params.clone(),
+        ValueType::Text,
+        &title.name,
+    )
+}
+
+fn email_property(email: &EmailAddress) -> Property {
+    let parameters = parameters(&email.v_card_params, &email.contexts, email.pref);
+    Property::new_email(&email.address, parameters)
+}
+
+fn online_service_property(service: &OnlineService) -> Option<Property> {
+    let mut parameters = parameters(&service.v_card_params, &service.contexts, service.pref);
+    set(&mut parameters, "service-type", service.service.as_ref());
+
+    let name = match service.v_card_name.as_deref() {
+        Some("impp") => "impp",
+        _ => "socialprofile",
+    };
+
+    Some(match (&service.uri, &service.user) {
+        (Some(uri), _) => Property::new(name, parameters, ValueType::Uri, uri),
+        (None, Some(user)) => Property::new("socialprofile", parameters, ValueType::Text, user),
+        (None, None) => return None,
+    })
+}
+
+fn phone_property(phone: &Phone) -> Property {
+    let mut parameters = parameters(&phone.v_card_params, &phone.contexts, phone.pref);
+    add_types(
+        &mut parameters,
+        phone.features.iter(),
+        |feature| match feature {
+            "mobile" => "cell",
+            "mainNumber" => "main-number",
+            feature => feature,
+        },
+    );
+
+    let value_type = if phone.number.contains(':') {
+        ValueType::Uri
+    } else {
+        ValueType::Text
+    };
+    Property::new("tel", parameters, value_type, &phone.number)
+}
+
+fn language_property(language: &LanguagePref) -> Property {
+    let parameters = parameters(&language.v_card_params, &language.contexts, language.pref);
+    Property::new(
+        "lang",
+        parameters,
+        ValueType::LanguageTag,
+        &language.language,
+    )
+}
+
+fn calendar_property(calendar: &Calendar) -> Property {
+    let mut parameters = parameters(&calendar.v_card_params, &calendar.contexts, calendar.pref);
+    set(&mut parameters, "mediatype", calendar.media_type.as_ref());
+
+    let name = match calendar.kind.as_deref() {
+        Some("freeBusy") => "fburl",
+        _ => "caluri",
+    };
+    Property::new(name, parameters, ValueType::Uri, &calendar.uri)
+}
+
+fn scheduling_property(address: &SchedulingAddress) -> Property {
+    let parameters = parameters(&address.v_card_params, &address.contexts, address.pref);
+    Property::new("caladruri", parameters, ValueType::Uri, &address.uri)
+}
+
+fn address_property(address: &Address) -> Property {
+    let mut parameters = parameters(&address.v_card_params, &address.contexts, address.pref);
+    set(&mut parameters, "label", address.full.as_ref());
+    set(&mut parameters, "cc", address.country_code.as_ref());
+    set(&mut parameters, "geo", address.coordinates.as_ref());
+    set(&mut parameters, "tz", address.time_zone.as_ref());
+
+    // Components without an ADR equivalent, such as the building number, are kept in the street component.
+    let mut components = vec![Vec::new(); ADDRESS_KINDS.len()];
+    let mut street = Vec::new();
+    for component in address.components.iter().filter(|c| c.kind != "separator") {
+        match ADDRESS_KINDS
+            .iter()
+            .position(|kind| *kind == component.kind)
+        {
+            Some(2) => street.push(component.value.clone()),
+            Some(index) => components[index].push(component.value.clone()),
+            None => street.push(component.value.clone()),
+        }
+    }
+    if !street.is_empty() {
+        let separator = address.default_separator.as_deref().unwrap_or(" ");
+        components[2].push(street.join(separator));
+    }
+
+    Property::new("adr", parameters, ValueType::Text, structured(components))
+}
+
+fn crypto_key_property(key: &CryptoKey) -> Property {
+    let mut parameters = parameters(&key.v_card_params, &key.contexts, key.pref);
+    set(&mut parameters, "mediatype", key.media_type.as_ref());
+    Property::new("key", parameters, ValueType::Uri, &key.uri)
+}
+
+fn directory_property(directory: &Directory) -> Property {
+    let mut parameters = parameters(
+        &directory.v_card_params,
+        &directory.contexts,
+        directory.pref,
+    );
+    set(&mut parameters, "mediatype", directory.media_type.as_ref());
+    set(&mut parameters, "index", directory.list_as);
+
+    let name = match directory.kind.as_deref() {
+        Some("directory") => "org-directory",
+        _ => "source",
+    };
+    Property::new(name, parameters, ValueType::Uri, &directory.uri)
+}
+
+fn link_property(link: &Link) -> Property {
+    let mut parameters = parameters(&link.v_card_params, &link.contexts, link.pref);
+    set(&mut parameters, "mediatype", link.media_type.as_ref());
+
+    let name = match link.kind.as_deref() {
+        Some("contact") => "contact-uri",
+        _ => "url",
+    };
+    Property::new(name, parameters, ValueType::Uri, &link.uri)
+}
+
+fn media_property(media: &Media) -> Property {
+    let mut parameters = parameters(&media.v_card_params, &media.contexts, media.pref);
+    set(&mut parameters, "mediatype", media.media_type.as_ref());
+
+    let name = match media.kind.as_str() {
+        "logo" => "logo",
+        "sound" => "sound",
+        _ => "photo",
+    };
+    Property::new(name, parameters, ValueType::Uri, &media.uri)
+}
+
+/// The date property of an anniversary, followed by its place if it is a birth or a death.
+fn anniversary_properties(anniversary: &Anniversary) -> Vec<Property> {
+    let (name, place) = match anniversary.kind.as_str() {
+        "birth" => ("bday", "birthplace"),
+        "death" => ("deathdate", "deathplace"),
+        _ => ("anniversary", ""),
+    };
+
+    let mut parameters = anniversary.v_card_params.clone();
+    let value = match &anniversary.date {
+        AnniversaryDate::Timestamp(timestamp) => Some(timestamp.utc.clone()),
+        AnniversaryDate::PartialDate(date) => {
+            set(&mut parameters, "calscale", date.calendar_scale.as_ref());
+            format_date(date)
+        }
+    };
+
+    let mut properties: Vec<Property> = value
+        .map(|value| Property::new(name, parameters, ValueType::DateAndOrTime, value))
+        .into_iter()
+        .collect();
+
+    let full = anniversary
+        .place
+        .as_ref()
+        .and_then(|place| place.full.as_ref());
+    if let (false, Some(full)) = (place.is_empty(), full) {
+        properties.push(Property::new(place, None, ValueType::Text, full));
+    }
+
+    properties
+}
+
+fn note_property(note: &Note) -> Property {
+    let mut parameters = note.v_card_params.clone();
+    set(&mut parameters, "created", note.created.as_ref());
+    if let Some(author) = &note.author {
+        set(&mut parameters, "author", author.uri.as_ref());
+        set(&mut parameters, "author-name", author.name.as_ref());
+    }
+    Property::new("note", parameters, ValueType::Text, &note.note)
+}
+
+fn personal_info_property(info: &PersonalInfo) -> Property {
+    let mut parameters = info.v_card_params.clone();
+    set(&mut parameters, "level", info.level.as_ref());
+    set(&mut parameters, "index", info.list_as);
+
+    let name = match info.kind.as_str() {
+        "hobby" => "hobby",
+        "interest" => "interest",
+        _ => "expertise",
+    };
+    Property::new(name, parameters, ValueType::Text, &info.value)
+}
@@ -0,0 +1,347 @@
+//! Best-effort jCard deserialization
+use std::{fmt::Display, marker::PhantomData};
+
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        Error, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserialize,
+};
+
+use crate::Vcard;
+
+/// A [`Vcard`] deserialized on a best-effort basis, together with the problems found in its properties.
+///
+/// Deserializing a [`Vcard`] fails on the first malformed property. A `LenientVcard` skips the properties that cannot
+/// be read and repairs the ones that can, recording a [`Diagnostic`] for each of them:
+///
+/// - parameter values that are numbers or booleans are converted to strings,
+/// - other parameter values that are not strings or arrays of strings are dropped,
+/// - properties that are still malformed (e.g. with fewer than 4 elements or an invalid date) are skipped,
+/// - an invalid `version` property is skipped, leaving [`Vcard::version`] empty.
+///
+/// Only a jCard that is not an array starting with `"vcard"` and an array of properties fails to deserialize.
+///
+/// ```rust
+/// # use vicardi::*;
+/// # use serde_json::json;
+/// # fn main() -> anyhow::Result<()> {
+/// let json = json!([
+///     "vcard",
+///     [
+///         ["version", {}, "text", "4.0"],
+///         ["fn", {}, "text", "Vicardi"],
+///         ["tel", {"pref": 1}, "uri", "tel:+998-71-123-45-67"],
+///         ["email", {}, "text"],
+///         ["bday", {}, "date", "yesterday"],
+///     ]
+/// ]);
+/// assert!(serde_json::from_value::<Vcard>(json.clone()).is_err());
+///
+/// let LenientVcard { vcard, diagnostics } = serde_json::from_value(json)?;
+/// assert_eq!(vcard.properties.len(), 2);
+/// assert_eq!(vcard.properties[1].parameters.pref(), Some(1));
+///
+/// let skipped: Vec<_> = diagnostics.iter().map(|d| (d.index, d.name.as_deref())).collect();
+/// assert_eq!(skipped, [(2, Some("tel")), (3, Some("email")), (4, Some("bday"))]);
+/// assert_eq!(
+///     diagnostics[0].to_string(),
+///     r#"property 2 ("tel"): the "pref" parameter value 1 is not a string, converted it to "1""#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LenientVcard {
+    pub vcard: Vcard,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl LenientVcard {
+    pub fn into_inner(self) -> Vcard {
+        self.vcard
+    }
+}
+
+/// A problem with a property found by [`LenientVcard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The index of the property in the jCard properties array, counting the `version` property.
+    pub index: usize,
+    /// The property name, if the property has one.
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "property {} ({name:?}): {}", self.index, self.reason),
+            None => write!(f, "property {}: {}", self.index, self.reason),
+        }
+    }
+}
+
+/// The diagnostics of a jCard being deserialized.
+#[derive(Default)]
+pub(crate) struct LenientLog {
+    pub(crate) diagnostics: Vec<Diagnostic>,
+    /// The index in the jCard properties array of each property that was kept.
+    pub(crate) indices: Vec<usize>,
+}
+
+impl LenientLog {
+    pub(crate) fn push(&mut self, index: usize, name: Option<&str>, reason: impl ToString) {
+        self.diagnostics.push(Diagnostic {
+            index,
+            name: name.map(str::to_string),
+            reason: reason.to_string(),
+        })
+    }
+}
+
+/// Any self-describing value, buffered so that a malformed property can be skipped without failing the whole jCard.
+///
+/// Unlike `serde_json::Value`, maps keep the order of their entries.
+#[derive(Debug, Clone)]
+pub(crate) enum Raw {
+    Null,
+    Bool(bool),
+    Signed(i64),
+    Unsigned(u64),
+    Float(f64),
+    String(String),
+    Seq(Vec<Raw>),
+    Map(Vec<(Raw, Raw)>),
+}
+
+impl Raw {
+    /// The name of a property, the first element of its array.
+    pub(crate) fn property_name(&self) -> Option<&str> {
+        match self {
+            Self::Seq(elements) => match elements.first() {
+                Some(Self::String(name)) => Some(name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Converts the scalar parameter values of a property to strings and drops the other invalid ones.
+    pub(crate) fn repair_parameters(&mut self, mut report: impl FnMut(String)) {
+        let Self::Seq(elements) = self else {
+            return;
+        };
+        let Some(Self::Map(parameters)) = elements.get_mut(1) else {
+            return;
+        };
+
+        parameters.retain_mut(|(key, value)| {
+            let key = match key {
+                Self::String(key) => key.as_str(),
+                _ => return true,
+            };
+
+            match value {
+                Self::String(_) => true,
+                Self::Seq(values) if values.iter().all(|value| value.as_string().is_some()) => {
+                    for value in values.iter_mut() {
+                        if let Some(string) = value.as_string() {
+                            if !matches!(value, Self::String(_)) {
+                                report(format!(
+                                    "the {key:?} parameter value {value} is not a string, converted it to {string:?}"
+                                ));
+                                *value = Self::String(string);
+                            }
+                        }
+                    }
+                    true
+                }
+                _ => match value.as_string() {
+                    Some(string) => {
+                        report(format!(
+                            "the {key:?} parameter value {value} is not a string, converted it to {string:?}"
+                        ));
+                        *value = Self::String(string);
+                        true
+                    }
+                    None => {
+                        report(format!("the {key:?} parameter value {value} is not a string, dropped it"));
+                        false
+                    }
+                },
+            }
+        });
+    }
+
+    /// The string form of a string, number or boolean.
+    fn as_string(&self) -> Option<String> {
+        match self {
+            Self::Bool(boolean) => Some(boolean.to_string()),
+            Self::Signed(int) => Some(int.to_string()),
+            Self::Unsigned(int) => Some(int.to_string()),
+            Self::Float(float) => Some(float.to_string()),
+            Self::String(string) => Some(string.clone()),
+            Self::Null | Self::Seq(_) | Self::Map(_) => None,
+        }
+    }
+}
+
+impl Display for Raw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(boolean) => write!(f, "{boolean}"),
+            Self::Signed(int) => write!(f, "{int}"),
+            Self::Unsigned(int) => write!(f, "{int}"),
+            Self::Float(float) => write!(f, "{float}"),
+            Self::String(string) => write!(f, "{string:?}"),
+            Self::Seq(_) => write!(f, "array"),
+            Self::Map(_) => write!(f, "map"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Raw {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(RawVisitor)
+    }
+}
+
+struct RawVisitor;
+
+impl<'de> Visitor<'de> for RawVisitor {
+    type Value = Raw;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Raw::Bool(v))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Raw::Signed(v))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Raw::Unsigned(v))
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Raw::Float(v))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Raw::String(v.to_string()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Raw::String(v))
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(Raw::Null)
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(Raw::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Raw::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        Ok(Raw::Seq(elements))
+    }
+
+    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: serde::de::MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some(entry) = access.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Raw::Map(entries))
+    }
+}
+
+/// Deserializes a type from a buffered [`Raw`] value.
+pub(crate) struct RawDeserializer<E>(Raw, PhantomData<E>);
+
+impl<E> RawDeserializer<E> {
+    pub(crate) fn new(raw: Raw) -> Self {
+        Self(raw, PhantomData)
+    }
+}
+
+impl<'de, E: Error> IntoDeserializer<'de, E> for Raw {
+    type Deserializer = RawDeserializer<E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        RawDeserializer::new(self)
+    }
+}
+
+impl<'de, E: Error> serde::Deserializer<'de> for RawDeserializer<E> {
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Raw::Null => visitor.visit_unit(),
+            Raw::Bool(boolean) => visitor.visit_bool(boolean),
+            Raw::Signed(int) => visitor.visit_i64(int),
+            Raw::Unsigned(int) => visitor.visit_u64(int),
+            Raw::Float(float) => visitor.visit_f64(float),
+            Raw::String(string) => visitor.visit_string(string),
+            Raw::Seq(elements) => {
+                let mut seq = SeqDeserializer::new(elements.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Raw::Map(entries) => {
+                let mut map = MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Raw::Null => visitor.visit_none(),
+            raw => visitor.visit_some(RawDeserializer::<E>::new(raw)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
@@ -38,27 +38,101 @@
 //!
 //! **A note on the version property:**
 //!
-//! The RFC requires that the first element in the array is a version property. At the moment, this crate does not
-//! enforce any rules regarding the position or number of version properties. However, the first occurance of the
-//! version property is removed from the array during deserialization. The value of the version is stored in the
-//! [`Vcard::version`] field.
+//! The RFC requires that the first element in the array is a version property. By default, this crate does not
+//! enforce any rules regarding the position or number of version properties, these are only checked when
+//! deserializing a [`Strict<Vcard>`]. However, the first occurance of the version property is removed from the array
+//! during deserialization. The value of the version is stored in the [`Vcard::version`] field.
 //!
 //! During serialization, the value of [`Vcard::version`] is placed at index 0 in the properties array.
+//!
+//! **A note on deserialization errors:**
+//!
+//! Errors in a property are prefixed with the path to the offending element in the jCard and the name of the
+//! property, e.g. `[1][42][1]["type"] ("tel"): `:
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # fn main() -> anyhow::Result<()> {
+//! let json = r#"["vcard",[["version",{},"text","4.0"],["tel",{"type":["work",1]},"uri","tel:+1"]]]"#;
+//! let error = serde_json::from_str::<Vcard>(json).unwrap_err();
+//! assert!(error.to_string().starts_with(r#"[1][1][1]["type"] ("tel"): "#));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Other formats
+//!
+//! - Plain-text vCard (`.vcf`) files can be read and written with [`Vcard::from_vcf`] and [`Vcard::to_vcf`]. See
+//!   the [`vcf`] module for details.
+//! - xCard (vCard in XML format) documents can be read and written with [`Vcard::from_xcard`] and
+//!   [`Vcard::to_xcard`]. See the [`xcard`] module for details.
+//! - JSContact (RFC 9553) cards can be converted to and from a [`Vcard`] with the [`From`] implementations of
+//!   [`jscontact::Card`]. See the [`jscontact`] module for details.
+//! - vCard 3.0 and 2.1 content can be converted to and from vCard 4.0 with [`Vcard::upgrade_to_v4`] and
+//!   [`Vcard::downgrade_to_v3`].
+//! - EPP contact objects (RFC 5733) can be mapped to and from a [`Vcard`] with [`epp::Contact`]. See the [`epp`]
+//!   module for details.
+//! - WHOIS contact blocks can be rendered with [`Vcard::to_whois`]. See the [`whois`] module for details.
+//! - RDAP (RFC 9083) entity objects embedding a [`Vcard`] are available in the `rdap` module with the `rdap` Cargo
+//!   feature.
 use serde::Deserialize;
 use serde_with::serde_as;
-use std::collections::HashMap;
 
 mod de;
 mod ser;
+mod xml;
 
 #[doc(hidden)]
 #[macro_use]
 pub mod macros;
 
+pub mod jscontact;
+
+pub use epp::EppError;
+pub mod epp;
+
+pub use error::Error;
+pub mod error;
+
+pub use datetime::DateTimeError;
+pub mod datetime;
+
 pub use structured::*;
 pub mod structured;
 
-pub type Parameters = HashMap<String, Vec<String>>;
+pub use vcf::{VcfError, VcfErrorKind};
+pub mod vcf;
+
+pub use version::{ConversionReport, LossyChange};
+pub mod version;
+
+pub use lenient::{Diagnostic, LenientVcard};
+pub mod lenient;
+
+pub use lossless::LosslessVcard;
+pub mod lossless;
+
+pub use parameters::{Parameters, Pid, TypeParam};
+pub mod parameters;
+
+pub use property_name::{Cardinality, PropertyName};
+pub mod property_name;
+
+#[cfg(feature = "rdap")]
+pub mod rdap;
+
+pub mod registry;
+
+pub use validate::Violation;
+pub mod validate;
+
+pub use value_type::ValueType;
+pub mod value_type;
+
+pub use xcard::XcardError;
+pub mod xcard;
+
+pub mod whois;
 
 /// A jCard serde type
 #[derive(Debug, Clone, PartialEq)]
@@ -83,6 +157,64 @@ impl Default for Vcard {
     }
 }
 
+/// Deserializes `T` strictly, rejecting anything that does not conform to
+/// [RFC 7095](https://datatracker.ietf.org/doc/html/rfc7095) and [RFC 6350](https://datatracker.ietf.org/doc/html/rfc6350).
+///
+/// By default, values are converted to the variant of their value type where possible (e.g. `1` in a `float`
+/// property becomes [`PropertyValue::Float`]) and kept as they are otherwise. A strict [`Property`] additionally
+/// rejects:
+///
+/// - values that do not match the property's value type,
+/// - property and parameter names that are not lowercase,
+/// - value types that are neither registered nor `x-` extensions,
+/// - structured values nested more than two levels deep.
+///
+/// A strict [`Vcard`] also requires exactly one `version` property, with the `"4.0"` text value, as the first
+/// property.
+///
+/// ```rust
+/// # use vicardi::*;
+/// # use serde_json::json;
+/// # fn main() -> anyhow::Result<()> {
+/// let json = json!(["x-score", {}, "float", 1]);
+///
+/// let property: Property = serde_json::from_value(json.clone())?;
+/// assert_eq!(property.values, vec![PropertyValue::Float(1.0)]);
+/// assert_eq!(serde_json::to_value(&property)?, json!(["x-score", {}, "float", 1.0]));
+///
+/// let Strict(property) = serde_json::from_value::<Strict<Property>>(json)?;
+/// assert_eq!(property.values, vec![PropertyValue::Float(1.0)]);
+///
+/// let json = json!(["vcard", [["version", {}, "text", "4.0"], ["x-score", {}, "float", "high"]]]);
+/// assert!(serde_json::from_value::<Vcard>(json.clone()).is_ok());
+/// assert!(serde_json::from_value::<Strict<Vcard>>(json).is_err());
+///
+/// let json = json!(["vcard", [["fn", {}, "text", "Vicardi"], ["version", {}, "text", "4.0"]]]);
+/// assert_eq!(
+///     serde_json::from_value::<Strict<Vcard>>(json).unwrap_err().to_string(),
+///     "the version property must be the first property, found it at index 1"
+/// );
+///
+/// let json = json!(["vcard", [["version", {}, "text", "4.0"], ["FN", {}, "text", "Vicardi"]]]);
+/// assert_eq!(
+///     serde_json::from_value::<Strict<Vcard>>(json).unwrap_err().to_string(),
+///     r#"[1][1][0] ("FN"): invalid value: string "FN", expected a lowercase property name"#
+/// );
+///
+/// let json = json!(["vcard", [["fn", {}, "text", "Vicardi"]]]);
+/// assert!(serde_json::from_value::<Strict<Vcard>>(json).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strict<T>(pub T);
+
+impl<T> Strict<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
 /// An entry in the jCard.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Property {
@@ -116,8 +248,8 @@ pub struct Property {
     /// ```
     pub parameters: Parameters,
 
-    /// The value type. E.g. `"text"`
-    pub value_type: String,
+    /// The value type. E.g. [`ValueType::Text`], serialized as `"text"`.
+    pub value_type: ValueType,
 
     /// Either a single or multiple values of the jCard property.
     ///
@@ -211,6 +343,52 @@ impl Vcard {
     pub fn push(&mut self, property: impl Into<Property>) {
         self.properties.push(property.into());
     }
+
+    /// The names of the property groups in the vCard, in the order they first appear.
+    ///
+    /// ```rust
+    /// # use vicardi::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let vcard = Vcard::from_vcf(
+    ///     "BEGIN:VCARD\r\n\
+    ///      VERSION:4.0\r\n\
+    ///      FN:John Doe\r\n\
+    ///      item1.TEL:tel:+1-555-555-5555\r\n\
+    ///      item1.X-ABLabel:Office\r\n\
+    ///      item2.EMAIL:john@example.com\r\n\
+    ///      END:VCARD\r\n",
+    /// )?;
+    ///
+    /// assert_eq!(vcard.groups().collect::<Vec<_>>(), ["item1", "item2"]);
+    ///
+    /// let names: Vec<_> = vcard.group("ITEM1").map(|property| property.name.as_str()).collect();
+    /// assert_eq!(names, ["tel", "x-ablabel"]);
+    /// assert_eq!(vcard.properties[1].group(), Some("item1"));
+    ///
+    /// assert!(vcard.to_vcf().contains("\r\nitem1.X-ABLABEL:Office\r\n"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        let mut seen: Vec<&str> = Vec::new();
+        self.properties.iter().filter_map(move |property| {
+            let group = property.group()?;
+            if seen.iter().any(|seen| seen.eq_ignore_ascii_case(group)) {
+                return None;
+            }
+            seen.push(group);
+            Some(group)
+        })
+    }
+
+    /// The properties in a group, compared case-insensitively.
+    pub fn group<'a>(&'a self, group: &'a str) -> impl Iterator<Item = &'a Property> {
+        self.properties.iter().filter(move |property| {
+            property
+                .group()
+                .is_some_and(|name| name.eq_ignore_ascii_case(group))
+        })
+    }
 }
 
 impl Property {
@@ -218,7 +396,7 @@ impl Property {
     pub fn new(
         name: impl ToString,
         parameters: impl Into<Option<Parameters>>,
-        value_type: impl ToString,
+        value_type: impl Into<ValueType>,
         value: impl Into<PropertyValue>,
     ) -> Self {
         Self::new_multivalued(name, parameters, value_type, vec![value.into()])
@@ -227,17 +405,88 @@ impl Property {
     pub fn new_multivalued(
         name: impl ToString,
         parameters: impl Into<Option<Parameters>>,
-        value_type: impl ToString,
+        value_type: impl Into<ValueType>,
         values: Vec<PropertyValue>,
     ) -> Self {
         Self {
             name: name.to_string(),
             parameters: parameters.into().unwrap_or_default(),
-            value_type: value_type.to_string(),
+            value_type: value_type.into(),
             values,
         }
     }
 
+    /// The [`PropertyName`] of [`Property::name`], parsed case-insensitively.
+    pub fn property_name(&self) -> PropertyName {
+        self.name.as_str().into()
+    }
+
+    /// The property group from the `group` parameter, e.g. `"item1"` for `item1.TEL` in a vCard.
+    pub fn group(&self) -> Option<&str> {
+        match self.parameters.get("group").map(Vec::as_slice) {
+            Some([group]) => Some(group),
+            _ => None,
+        }
+    }
+
+    /// Sets the `group` parameter. A new `group` parameter is inserted as the first parameter.
+    ///
+    /// The group must be made of letters, digits and dashes, as it is written before the property name in a vCard.
+    ///
+    /// ```rust
+    /// # use vicardi::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut property = Property::new_fn("John Doe", None);
+    /// property.set_group("item-1")?;
+    /// assert_eq!(property.group(), Some("item-1"));
+    /// assert_eq!(property.set_group("x:evil"), Err(Error::InvalidGroup("x:evil".into())));
+    /// assert_eq!(property.group(), Some("item-1"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_group(&mut self, group: impl ToString) -> Result<(), Error> {
+        let group = group.to_string();
+        if !is_group(&group) {
+            return Err(Error::InvalidGroup(group));
+        }
+
+        match self.parameters.get_mut("group") {
+            Some(values) => *values = vec![group],
+            None => {
+                self.parameters.shift_insert(0, "group".into(), vec![group]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the `group` parameter, returning the group.
+    pub fn remove_group(&mut self) -> Option<String> {
+        let group = self.group()?.to_string();
+        self.parameters.shift_remove("group");
+        Some(group)
+    }
+
+    /// The single non-empty text value, or the first component of a structured value such as `org`.
+    pub(crate) fn text(&self) -> Option<&str> {
+        let value = match self.values.as_slice() {
+            [PropertyValue::Structured(components)] => components.first()?,
+            [value] => value,
+            _ => return None,
+        };
+        match value {
+            PropertyValue::String(value) if !value.is_empty() => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The number and `;ext=` extension of a single `tel` value, without the `tel:` scheme.
+    pub(crate) fn tel_number(&self) -> Option<(&str, Option<&str>)> {
+        match self.values.as_slice() {
+            [PropertyValue::String(value)] => Some(tel_number(value)),
+            _ => None,
+        }
+    }
+
     /// # Example
     ///
     /// ```rust
@@ -263,7 +512,7 @@ impl Property {
     /// # }
     /// ```
     pub fn new_fn(formatted: impl ToString, parameters: impl Into<Option<Parameters>>) -> Self {
-        Self::new("fn", parameters, "text", formatted)
+        Self::new("fn", parameters, ValueType::Text, formatted)
     }
 
     /// # Example
@@ -322,7 +571,7 @@ impl Property {
     /// # }
     /// ```
     pub fn new_adr(address: Address, parameters: impl Into<Option<Parameters>>) -> Self {
-        Self::new("adr", parameters, "text", address)
+        Self::new("adr", parameters, ValueType::Text, address)
     }
 
     /// # Example
@@ -355,7 +604,7 @@ impl Property {
         org: impl Into<PropertyValue>,
         parameters: impl Into<Option<Parameters>>,
     ) -> Self {
-        Self::new("org", parameters, "text", org)
+        Self::new("org", parameters, ValueType::Text, org)
     }
 
     /// # Example
@@ -389,7 +638,12 @@ impl Property {
         let mut parameters = parameters.into().unwrap_or_default();
         parameters.insert("type".into(), vec![phone_type.into().to_string()]);
 
-        Self::new("tel", parameters, "uri", format!("tel:{}", number.as_ref()))
+        Self::new(
+            "tel",
+            parameters,
+            ValueType::Uri,
+            format!("tel:{}", number.as_ref()),
+        )
     }
 
     /// # Example
@@ -416,7 +670,7 @@ impl Property {
     /// # }
     /// ```
     pub fn new_email(email: impl ToString, parameters: impl Into<Option<Parameters>>) -> Self {
-        Self::new("email", parameters, "text", email.to_string())
+        Self::new("email", parameters, ValueType::Text, email.to_string())
     }
 }
 
@@ -428,3 +682,18 @@ where
         PropertyValue::String(value.to_string())
     }
 }
+
+/// The number and `;ext=` extension of a `tel:` URI or a plain number, without the scheme and other URI parameters.
+pub(crate) fn tel_number(value: &str) -> (&str, Option<&str>) {
+    let value = value.strip_prefix("tel:").unwrap_or(value);
+    let (number, parameters) = value.split_once(';').unwrap_or((value, ""));
+    let ext = parameters
+        .split(';')
+        .find_map(|parameter| parameter.strip_prefix("ext="));
+    (number, ext)
+}
+
+/// Whether the name is a property group, `1*(ALPHA / DIGIT / "-")`.
+pub(crate) fn is_group(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
@@ -0,0 +1,129 @@
+//! Byte-for-byte stable jCard round-trips
+use std::ops::{Deref, DerefMut};
+
+use crate::{Property, PropertyValue, Vcard};
+
+/// A [`Vcard`] that serializes back to the exact jCard it was deserialized from.
+///
+/// Deserializing a [`Vcard`] normalizes the jCard: single values written as arrays (`{"type": ["work"]}`, `["x"]`)
+/// become plain strings, numbers are converted to the number type of the property, and the `version` property is
+/// moved to the front. A `LosslessVcard` records these choices while deserializing and applies them again when
+/// serialized, so a card that passes through unmodified produces the same JSON.
+///
+/// The recorded layout is kept when the card is modified, as long as it still fits: a property's layout is only
+/// applied while the property at that position has the same name.
+///
+/// ```rust
+/// # use vicardi::*;
+/// # fn main() -> anyhow::Result<()> {
+/// let json = r#"["vcard",[["fn",{"type":["work"]},"text",["Vicardi"]],["VERSION",{},"text","4.0"],["x-score",{},"float",1]]]"#;
+///
+/// let vcard: Vcard = serde_json::from_str(json)?;
+/// assert_ne!(serde_json::to_string(&vcard)?, json);
+///
+/// let mut lossless: LosslessVcard = serde_json::from_str(json)?;
+/// assert_eq!(lossless.version, "4.0");
+/// assert_eq!(lossless.properties.len(), 2);
+/// assert_eq!(serde_json::to_string(&lossless)?, json);
+///
+/// lossless.version = "3.0".into();
+/// assert_eq!(
+///     serde_json::to_string(&lossless)?,
+///     json.replace(r#""text","4.0""#, r#""text","3.0""#)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LosslessVcard {
+    pub vcard: Vcard,
+    pub(crate) layout: VcardLayout,
+}
+
+impl LosslessVcard {
+    pub fn into_inner(self) -> Vcard {
+        self.vcard
+    }
+}
+
+impl From<Vcard> for LosslessVcard {
+    fn from(vcard: Vcard) -> Self {
+        Self {
+            vcard,
+            layout: VcardLayout::default(),
+        }
+    }
+}
+
+impl From<LosslessVcard> for Vcard {
+    fn from(lossless: LosslessVcard) -> Self {
+        lossless.vcard
+    }
+}
+
+impl Deref for LosslessVcard {
+    type Target = Vcard;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vcard
+    }
+}
+
+impl DerefMut for LosslessVcard {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.vcard
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct VcardLayout {
+    pub(crate) version: VersionLayout,
+    /// The layout of each of [`Vcard::properties`].
+    pub(crate) properties: Vec<PropertyLayout>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) enum VersionLayout {
+    /// `["version", {}, "text", version]` first, as a [`Vcard`] is serialized.
+    #[default]
+    Default,
+    /// The jCard had no `version` property.
+    Missing,
+    /// The `version` property as it was deserialized.
+    Source {
+        index: usize,
+        version: String,
+        property: Box<Property>,
+        layout: PropertyLayout,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct PropertyLayout {
+    pub(crate) name: String,
+    /// The parameters written as an array.
+    pub(crate) arrays: Vec<String>,
+    pub(crate) values: Vec<ValueLayout>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ValueLayout {
+    Scalar,
+    Integer,
+    Float,
+    Array(Vec<ValueLayout>),
+}
+
+impl ValueLayout {
+    /// The layout of a value before it is coerced to its value type.
+    pub(crate) fn of(value: &PropertyValue) -> Self {
+        match value {
+            PropertyValue::String(_) | PropertyValue::Bool(_) => Self::Scalar,
+            PropertyValue::Integer(_) => Self::Integer,
+            PropertyValue::Float(_) => Self::Float,
+            PropertyValue::Structured(components) => {
+                Self::Array(components.iter().map(Self::of).collect())
+            }
+        }
+    }
+}
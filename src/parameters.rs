@@ -0,0 +1,459 @@
+//! The parameters of a jCard property
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    fmt::Display,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use indexmap::IndexMap;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+/// The parameters of a [`crate::Property`], a map from the lowercase parameter name to its values.
+///
+/// The map is (de)serialized the way jCard does, with single values as plain strings. Parameters keep their insertion
+/// order, so the same property always serializes to the same JSON. Any parameter, including unknown and `x-`
+/// parameters, is kept in the map; the accessors only interpret the parameters defined by
+/// [RFC 6350, Section 5](https://datatracker.ietf.org/doc/html/rfc6350#section-5).
+///
+/// Removing a parameter with [`IndexMap::shift_remove`] keeps the order of the others.
+///
+/// ```rust
+/// # use vicardi::*;
+/// # fn main() -> anyhow::Result<()> {
+/// let property: Property = serde_json::from_str(
+///     r#"["tel", {"type": ["work", "voice"], "pref": "1", "pid": ["1.1", "2"], "x-carrier": "beeline"}, "uri",
+///     "tel:+998-71-123-45-67"]"#,
+/// )?;
+///
+/// let parameters = &property.parameters;
+/// assert_eq!(parameters.pref(), Some(1));
+/// assert_eq!(parameters.types().collect::<Vec<_>>(), [TypeParam::Work, TypeParam::Voice]);
+/// assert_eq!(parameters.pids().map(|pid| pid.to_string()).collect::<Vec<_>>(), ["1.1", "2"]);
+/// assert_eq!(parameters["x-carrier"], ["beeline"]);
+///
+/// assert_eq!(
+///     serde_json::to_string(&property.parameters)?,
+///     r#"{"type":["work","voice"],"pref":"1","pid":["1.1","2"],"x-carrier":"beeline"}"#,
+/// );
+///
+/// let parameters = parameters! {"pref" => "101", "language" => "uz"};
+/// assert_eq!(parameters.pref(), None);
+/// assert_eq!(parameters.language(), Some("uz"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Parameters(IndexMap<String, Vec<String>>);
+
+impl Parameters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_inner(self) -> IndexMap<String, Vec<String>> {
+        self.0
+    }
+
+    /// The first value of a parameter.
+    fn first(&self, key: &str) -> Option<&str> {
+        self.0.get(key)?.first().map(String::as_str)
+    }
+
+    /// The `PREF` parameter, if it is an integer between 1 and 100.
+    pub fn pref(&self) -> Option<u8> {
+        self.first("pref")?
+            .parse()
+            .ok()
+            .filter(|pref| (1..=100).contains(pref))
+    }
+
+    /// The values of the `TYPE` parameter, including the ones joined by commas.
+    pub fn types(&self) -> impl Iterator<Item = TypeParam> + '_ {
+        self.0
+            .get("type")
+            .into_iter()
+            .flatten()
+            .flat_map(|types| types.split(','))
+            .map(TypeParam::from)
+    }
+
+    /// The `LANGUAGE` parameter.
+    pub fn language(&self) -> Option<&str> {
+        self.first("language")
+    }
+
+    /// The `ALTID` parameter.
+    pub fn altid(&self) -> Option<&str> {
+        self.first("altid")
+    }
+
+    /// The valid values of the `PID` parameter.
+    pub fn pids(&self) -> impl Iterator<Item = Pid> + '_ {
+        self.0
+            .get("pid")
+            .into_iter()
+            .flatten()
+            .flat_map(|pids| pids.split(','))
+            .filter_map(Pid::parse)
+    }
+
+    /// The values of the `SORT-AS` parameter, one for each component of the property value.
+    pub fn sort_as(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .get("sort-as")
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// The `GEO` parameter, a URI.
+    pub fn geo(&self) -> Option<&str> {
+        self.first("geo")
+    }
+
+    /// The `TZ` parameter, a time zone name, UTC offset or URI.
+    pub fn tz(&self) -> Option<&str> {
+        self.first("tz")
+    }
+
+    /// The `LABEL` parameter of an `ADR` property.
+    pub fn label(&self) -> Option<&str> {
+        self.first("label")
+    }
+
+    /// The `CALSCALE` parameter.
+    pub fn calscale(&self) -> Option<&str> {
+        self.first("calscale")
+    }
+
+    /// The `MEDIATYPE` parameter.
+    pub fn mediatype(&self) -> Option<&str> {
+        self.first("mediatype")
+    }
+}
+
+impl Deref for Parameters {
+    type Target = IndexMap<String, Vec<String>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Parameters {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<IndexMap<String, Vec<String>>> for Parameters {
+    fn from(map: IndexMap<String, Vec<String>>) -> Self {
+        Self(map)
+    }
+}
+
+impl From<Parameters> for IndexMap<String, Vec<String>> {
+    fn from(parameters: Parameters) -> Self {
+        parameters.0
+    }
+}
+
+impl FromIterator<(String, Vec<String>)> for Parameters {
+    fn from_iter<T: IntoIterator<Item = (String, Vec<String>)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<(String, Vec<String>)> for Parameters {
+    fn extend<T: IntoIterator<Item = (String, Vec<String>)>>(&mut self, iter: T) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for Parameters {
+    type Item = (String, Vec<String>);
+    type IntoIter = indexmap::map::IntoIter<String, Vec<String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Parameters {
+    type Item = (&'a String, &'a Vec<String>);
+    type IntoIter = indexmap::map::Iter<'a, String, Vec<String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Parameters {
+    type Item = (&'a String, &'a mut Vec<String>);
+    type IntoIter = indexmap::map::IterMut<'a, String, Vec<String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+/// A value of the `TYPE` parameter, as defined by
+/// [RFC 6350, Section 5.6](https://datatracker.ietf.org/doc/html/rfc6350#section-5.6) and the `TEL` and `RELATED`
+/// properties.
+///
+/// Values are parsed case-insensitively. Any other value is kept lowercased in [`TypeParam::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
+pub enum TypeParam {
+    Work,
+    Home,
+    // TEL
+    Text,
+    Voice,
+    Fax,
+    Cell,
+    Video,
+    Pager,
+    Textphone,
+    // RELATED
+    Contact,
+    Acquaintance,
+    Friend,
+    Met,
+    CoWorker,
+    Colleague,
+    CoResident,
+    Neighbor,
+    Child,
+    Parent,
+    Sibling,
+    Spouse,
+    Kin,
+    Muse,
+    Crush,
+    Date,
+    Sweetheart,
+    Me,
+    Agent,
+    Emergency,
+    /// Any other type, lowercased.
+    Other(String),
+}
+
+impl Display for TypeParam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl AsRef<str> for TypeParam {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Work => "work",
+            Self::Home => "home",
+            Self::Text => "text",
+            Self::Voice => "voice",
+            Self::Fax => "fax",
+            Self::Cell => "cell",
+            Self::Video => "video",
+            Self::Pager => "pager",
+            Self::Textphone => "textphone",
+            Self::Contact => "contact",
+            Self::Acquaintance => "acquaintance",
+            Self::Friend => "friend",
+            Self::Met => "met",
+            Self::CoWorker => "co-worker",
+            Self::Colleague => "colleague",
+            Self::CoResident => "co-resident",
+            Self::Neighbor => "neighbor",
+            Self::Child => "child",
+            Self::Parent => "parent",
+            Self::Sibling => "sibling",
+            Self::Spouse => "spouse",
+            Self::Kin => "kin",
+            Self::Muse => "muse",
+            Self::Crush => "crush",
+            Self::Date => "date",
+            Self::Sweetheart => "sweetheart",
+            Self::Me => "me",
+            Self::Agent => "agent",
+            Self::Emergency => "emergency",
+            Self::Other(other) => other.as_ref(),
+        }
+    }
+}
+
+impl FromStr for TypeParam {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "work" => Self::Work,
+            "home" => Self::Home,
+            "text" => Self::Text,
+            "voice" => Self::Voice,
+            "fax" => Self::Fax,
+            "cell" => Self::Cell,
+            "video" => Self::Video,
+            "pager" => Self::Pager,
+            "textphone" => Self::Textphone,
+            "contact" => Self::Contact,
+            "acquaintance" => Self::Acquaintance,
+            "friend" => Self::Friend,
+            "met" => Self::Met,
+            "co-worker" => Self::CoWorker,
+            "colleague" => Self::Colleague,
+            "co-resident" => Self::CoResident,
+            "neighbor" => Self::Neighbor,
+            "child" => Self::Child,
+            "parent" => Self::Parent,
+            "sibling" => Self::Sibling,
+            "spouse" => Self::Spouse,
+            "kin" => Self::Kin,
+            "muse" => Self::Muse,
+            "crush" => Self::Crush,
+            "date" => Self::Date,
+            "sweetheart" => Self::Sweetheart,
+            "me" => Self::Me,
+            "agent" => Self::Agent,
+            "emergency" => Self::Emergency,
+            other => Self::Other(other.to_string()),
+        })
+    }
+}
+
+impl From<&str> for TypeParam {
+    fn from(value: &str) -> Self {
+        let Ok(type_param) = value.parse();
+        type_param
+    }
+}
+
+impl From<String> for TypeParam {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+/// A value of the `PID` parameter, as defined by
+/// [RFC 6350, Section 5.5](https://datatracker.ietf.org/doc/html/rfc6350#section-5.5).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pid {
+    /// The identifier of the property instance.
+    pub id: u32,
+    /// The `CLIENTPIDMAP` source identifier, if any.
+    pub source: Option<u32>,
+}
+
+impl Pid {
+    fn parse(value: &str) -> Option<Self> {
+        let digits = |s: &str| {
+            (!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+                .then(|| s.parse().ok())
+                .flatten()
+        };
+
+        Some(match value.split_once('.') {
+            Some((id, source)) => Self {
+                id: digits(id)?,
+                source: Some(digits(source)?),
+            },
+            None => Self {
+                id: digits(value)?,
+                source: None,
+            },
+        })
+    }
+}
+
+impl Display for Pid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.source {
+            Some(source) => write!(f, "{}.{}", self.id, source),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+/// Escapes a parameter value for the text vCard format, as defined by
+/// [RFC 6868](https://datatracker.ietf.org/doc/html/rfc6868): `^` becomes `^^`, a newline becomes `^n` and `"` becomes
+/// `^'`.
+///
+/// jCard and xCard carry parameter values unescaped, so this is only needed when writing text vCards, which
+/// [`crate::Vcard::to_vcf`] does.
+///
+/// ```rust
+/// # use vicardi::*;
+/// # use vicardi::parameters::{caret_decode, caret_encode};
+/// # fn main() -> anyhow::Result<()> {
+/// assert_eq!(caret_encode("Mr. John Q. Public, Esq.\n123 \"Main\" St"), "Mr. John Q. Public, Esq.^n123 ^'Main^' St");
+/// assert_eq!(caret_decode("^^ ^N ^x"), "^ \n ^x");
+///
+/// let mut vcard = Vcard::default();
+/// vcard.push(Property::new_adr(
+///     Address::default(),
+///     parameters! {"label" => "123 Main St\nAnytown, CA 91921"},
+/// ));
+///
+/// let vcf = vcard.to_vcf();
+/// assert!(vcf.contains(r#"ADR;LABEL="123 Main St^nAnytown, CA 91921":;;;;;;"#));
+/// assert_eq!(Vcard::from_vcf(&vcf)?, vcard);
+/// # Ok(())
+/// # }
+/// ```
+pub fn caret_encode(value: &str) -> Cow<'_, str> {
+    if !value.contains(['^', '\n', '\r', '"']) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut out = String::with_capacity(value.len() + 2);
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '^' => out.push_str("^^"),
+            '"' => out.push_str("^'"),
+            '\n' => out.push_str("^n"),
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                out.push_str("^n");
+            }
+            c => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Unescapes a parameter value of a text vCard, the reverse of [`caret_encode`].
+///
+/// A `^` that is not followed by `^`, `n`, `N` or `'` is kept as it is.
+pub fn caret_decode(value: &str) -> Cow<'_, str> {
+    if !value.contains('^') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        let decoded = match (c, chars.peek()) {
+            ('^', Some('^')) => '^',
+            ('^', Some('n' | 'N')) => '\n',
+            ('^', Some('\'')) => '"',
+            (c, _) => {
+                out.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        out.push(decoded);
+    }
+    Cow::Owned(out)
+}
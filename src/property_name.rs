@@ -0,0 +1,311 @@
+//! The name of a jCard property
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+use crate::{registry, ValueType};
+
+/// A [`crate::Property::name`], as registered in the
+/// [IANA vCard Elements registry](https://www.iana.org/assignments/vcard-elements/vcard-elements.xhtml) by
+/// [RFC 6350](https://datatracker.ietf.org/doc/html/rfc6350), [RFC 6474](https://datatracker.ietf.org/doc/html/rfc6474)
+/// and [RFC 6715](https://datatracker.ietf.org/doc/html/rfc6715).
+///
+/// Names are parsed case-insensitively. Any other name, such as an `x-` extension, is kept lowercased in
+/// [`PropertyName::Extension`].
+///
+/// ```rust
+/// # use vicardi::*;
+/// # fn main() -> anyhow::Result<()> {
+/// assert_eq!("FN".parse::<PropertyName>()?, PropertyName::Fn);
+/// assert_eq!(PropertyName::from("X-Karma-Points"), PropertyName::Extension("x-karma-points".into()));
+///
+/// assert_eq!(PropertyName::Bday.default_value_type(), ValueType::DateAndOrTime);
+/// assert_eq!(PropertyName::from("x-karma-points").default_value_type(), ValueType::Unknown);
+///
+/// let property = Property::new(PropertyName::Email, None, ValueType::Text, "vicardi@example.com");
+/// assert_eq!(property.name, "email");
+/// assert_eq!(property.property_name(), PropertyName::Email);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
+pub enum PropertyName {
+    Version,
+    Source,
+    Kind,
+    Xml,
+    Fn,
+    N,
+    Nickname,
+    Photo,
+    Bday,
+    Anniversary,
+    Gender,
+    Adr,
+    Tel,
+    Email,
+    Impp,
+    Lang,
+    Tz,
+    Geo,
+    Title,
+    Role,
+    Logo,
+    Org,
+    Member,
+    Related,
+    Categories,
+    Note,
+    ProdId,
+    Rev,
+    Sound,
+    Uid,
+    ClientPidMap,
+    Url,
+    Key,
+    FbUrl,
+    CalAdrUri,
+    CalUri,
+    /// RFC 6474
+    Birthplace,
+    /// RFC 6474
+    Deathplace,
+    /// RFC 6474
+    Deathdate,
+    /// RFC 6715
+    Expertise,
+    /// RFC 6715
+    Hobby,
+    /// RFC 6715
+    Interest,
+    /// RFC 6715
+    OrgDirectory,
+    /// Any other property name, lowercased.
+    Extension(String),
+}
+
+impl PropertyName {
+    /// The jCard name of the property, e.g. `"fn"`.
+    pub fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+
+    /// How many times the property may appear in a vCard, from the [`crate::registry`]. Extension properties may
+    /// appear any number of times.
+    pub fn cardinality(&self) -> Cardinality {
+        registry::property(self.as_str()).map_or(Cardinality::Any, |spec| spec.cardinality)
+    }
+
+    /// The value type of the property when it is not specified, e.g. in a vCard without a `VALUE` parameter.
+    ///
+    /// Extension properties default to [`ValueType::Unknown`], as described in
+    /// [RFC 7095, Section 5](https://datatracker.ietf.org/doc/html/rfc7095#section-5).
+    pub fn default_value_type(&self) -> ValueType {
+        match self {
+            Self::Source
+            | Self::Photo
+            | Self::Impp
+            | Self::Geo
+            | Self::Logo
+            | Self::Member
+            | Self::Related
+            | Self::Sound
+            | Self::Uid
+            | Self::Url
+            | Self::Key
+            | Self::FbUrl
+            | Self::CalAdrUri
+            | Self::CalUri => ValueType::Uri,
+            Self::Bday | Self::Anniversary | Self::Deathdate => ValueType::DateAndOrTime,
+            Self::Rev => ValueType::Timestamp,
+            Self::Lang => ValueType::LanguageTag,
+            Self::Version
+            | Self::Kind
+            | Self::Xml
+            | Self::Fn
+            | Self::N
+            | Self::Nickname
+            | Self::Gender
+            | Self::Adr
+            | Self::Tel
+            | Self::Email
+            | Self::Tz
+            | Self::Title
+            | Self::Role
+            | Self::Org
+            | Self::Categories
+            | Self::Note
+            | Self::ProdId
+            | Self::ClientPidMap
+            | Self::Birthplace
+            | Self::Deathplace
+            | Self::Expertise
+            | Self::Hobby
+            | Self::Interest
+            | Self::OrgDirectory => ValueType::Text,
+            Self::Extension(_) => ValueType::Unknown,
+        }
+    }
+}
+
+/// How many times a property may appear in a vCard, as defined by
+/// [RFC 6350, Section 3.3](https://datatracker.ietf.org/doc/html/rfc6350#section-3.3).
+///
+/// Properties with the same `ALTID` parameter count as a single instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cardinality {
+    /// `1`
+    ExactlyOne,
+    /// `*1`
+    AtMostOne,
+    /// `1*`
+    AtLeastOne,
+    /// `*`
+    Any,
+}
+
+impl Cardinality {
+    /// Whether the property may appear `count` times.
+    pub fn allows(self, count: usize) -> bool {
+        match self {
+            Self::ExactlyOne => count == 1,
+            Self::AtMostOne => count <= 1,
+            Self::AtLeastOne => count >= 1,
+            Self::Any => true,
+        }
+    }
+}
+
+impl Display for Cardinality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::ExactlyOne => "1",
+            Self::AtMostOne => "*1",
+            Self::AtLeastOne => "1*",
+            Self::Any => "*",
+        })
+    }
+}
+
+impl Display for PropertyName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl AsRef<str> for PropertyName {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Version => "version",
+            Self::Source => "source",
+            Self::Kind => "kind",
+            Self::Xml => "xml",
+            Self::Fn => "fn",
+            Self::N => "n",
+            Self::Nickname => "nickname",
+            Self::Photo => "photo",
+            Self::Bday => "bday",
+            Self::Anniversary => "anniversary",
+            Self::Gender => "gender",
+            Self::Adr => "adr",
+            Self::Tel => "tel",
+            Self::Email => "email",
+            Self::Impp => "impp",
+            Self::Lang => "lang",
+            Self::Tz => "tz",
+            Self::Geo => "geo",
+            Self::Title => "title",
+            Self::Role => "role",
+            Self::Logo => "logo",
+            Self::Org => "org",
+            Self::Member => "member",
+            Self::Related => "related",
+            Self::Categories => "categories",
+            Self::Note => "note",
+            Self::ProdId => "prodid",
+            Self::Rev => "rev",
+            Self::Sound => "sound",
+            Self::Uid => "uid",
+            Self::ClientPidMap => "clientpidmap",
+            Self::Url => "url",
+            Self::Key => "key",
+            Self::FbUrl => "fburl",
+            Self::CalAdrUri => "caladruri",
+            Self::CalUri => "caluri",
+            Self::Birthplace => "birthplace",
+            Self::Deathplace => "deathplace",
+            Self::Deathdate => "deathdate",
+            Self::Expertise => "expertise",
+            Self::Hobby => "hobby",
+            Self::Interest => "interest",
+            Self::OrgDirectory => "org-directory",
+            Self::Extension(other) => other.as_ref(),
+        }
+    }
+}
+
+impl FromStr for PropertyName {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "version" => Self::Version,
+            "source" => Self::Source,
+            "kind" => Self::Kind,
+            "xml" => Self::Xml,
+            "fn" => Self::Fn,
+            "n" => Self::N,
+            "nickname" => Self::Nickname,
+            "photo" => Self::Photo,
+            "bday" => Self::Bday,
+            "anniversary" => Self::Anniversary,
+            "gender" => Self::Gender,
+            "adr" => Self::Adr,
+            "tel" => Self::Tel,
+            "email" => Self::Email,
+            "impp" => Self::Impp,
+            "lang" => Self::Lang,
+            "tz" => Self::Tz,
+            "geo" => Self::Geo,
+            "title" => Self::Title,
+            "role" => Self::Role,
+            "logo" => Self::Logo,
+            "org" => Self::Org,
+            "member" => Self::Member,
+            "related" => Self::Related,
+            "categories" => Self::Categories,
+            "note" => Self::Note,
+            "prodid" => Self::ProdId,
+            "rev" => Self::Rev,
+            "sound" => Self::Sound,
+            "uid" => Self::Uid,
+            "clientpidmap" => Self::ClientPidMap,
+            "url" => Self::Url,
+            "key" => Self::Key,
+            "fburl" => Self::FbUrl,
+            "caladruri" => Self::CalAdrUri,
+            "caluri" => Self::CalUri,
+            "birthplace" => Self::Birthplace,
+            "deathplace" => Self::Deathplace,
+            "deathdate" => Self::Deathdate,
+            "expertise" => Self::Expertise,
+            "hobby" => Self::Hobby,
+            "interest" => Self::Interest,
+            "org-directory" => Self::OrgDirectory,
+            other => Self::Extension(other.to_string()),
+        })
+    }
+}
+
+impl From<&str> for PropertyName {
+    fn from(value: &str) -> Self {
+        let Ok(name) = value.parse();
+        name
+    }
+}
+
+impl From<String> for PropertyName {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
@@ -0,0 +1,284 @@
+//! RDAP entity objects, enabled by the `rdap` Cargo feature
+//!
+//! An [RFC 9083](https://datatracker.ietf.org/doc/html/rfc9083) entity object represents a registrant, registrar,
+//! contact or other party in an RDAP response. Its contact information is a jCard in the `vcardArray` member, which is
+//! (de)serialized with the [`Vcard`] serde implementations.
+//!
+//! [`Role`] and [`Status`] values are checked against the
+//! [RDAP JSON Values registry](https://www.iana.org/assignments/rdap-json-values/rdap-json-values.xhtml), and an
+//! object with an `objectClassName` other than `"entity"` fails to deserialize.
+//! The [`icann`] module checks entity vCards against the ICANN gTLD RDAP Response Profile, and the [`redaction`]
+//! module redacts them.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use vicardi::rdap::*;
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new_fn("Uzinfocom", None));
+//!
+//! let mut registrar = Entity::new("REG-1", vcard);
+//! registrar.roles.push(Role::Registrar);
+//! registrar.public_ids.push(PublicId {
+//!     type_: "IANA Registrar ID".into(),
+//!     identifier: "1".into(),
+//! });
+//! registrar.status.push(Status::Active);
+//! registrar.rdap_conformance.push("rdap_level_0".into());
+//!
+//! let json = json!({
+//!     "objectClassName": "entity",
+//!     "handle": "REG-1",
+//!     "vcardArray": ["vcard", [["version", {}, "text", "4.0"], ["fn", {}, "text", "Uzinfocom"]]],
+//!     "roles": ["registrar"],
+//!     "publicIds": [{"type": "IANA Registrar ID", "identifier": "1"}],
+//!     "status": ["active"],
+//!     "rdapConformance": ["rdap_level_0"]
+//! });
+//! assert_eq!(serde_json::to_value(&registrar)?, json);
+//! assert_eq!(serde_json::from_value::<Entity>(json)?, registrar);
+//!
+//! let error = serde_json::from_value::<Entity>(json!({"objectClassName": "entity", "roles": ["owner"]}));
+//! assert!(error.unwrap_err().to_string().contains(r#"unknown RDAP role "owner""#));
+//! assert!(serde_json::from_value::<Entity>(json!({"objectClassName": "domain"})).is_err());
+//! # Ok(())
+//! # }
+//! ```
+use std::{fmt::Display, str::FromStr};
+
+use serde::{de::Unexpected, Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+use crate::{Error, Vcard};
+
+pub mod icann;
+pub mod redaction;
+
+/// An RDAP entity object.
+///
+/// Empty members are not serialized. `rdap_conformance`, `notices` and `redacted` only belong in the top-most object
+/// of a response.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entity {
+    object_class_name: EntityClass,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vcard_array: Option<Vcard>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<Role>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub public_ids: Vec<PublicId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<Entity>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remarks: Vec<Remark>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<Link>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<Event>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<Status>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port43: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notices: Vec<Remark>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rdap_conformance: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redacted: Vec<redaction::Redacted>,
+}
+
+impl Entity {
+    pub fn new(handle: impl ToString, vcard: Vcard) -> Self {
+        Self {
+            handle: Some(handle.to_string()),
+            vcard_array: Some(vcard),
+            ..Self::default()
+        }
+    }
+
+    /// The nested entities with the role, e.g. the abuse contacts of a registrar.
+    pub fn entities_with_role(&self, role: Role) -> impl Iterator<Item = &Entity> {
+        self.entities
+            .iter()
+            .filter(move |entity| entity.roles.contains(&role))
+    }
+}
+
+/// The `"entity"` `objectClassName`, rejecting any other class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct EntityClass;
+
+impl Serialize for EntityClass {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str("entity")
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityClass {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match String::deserialize(deserializer)?.as_str() {
+            "entity" => Ok(Self),
+            other => Err(serde::de::Error::invalid_value(
+                Unexpected::Str(other),
+                &r#"the "entity" object class name"#,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicId {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub identifier: String,
+}
+
+/// A remark or notice.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Remark {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    pub description: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Link {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rel: Option<String>,
+    pub href: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hreflang: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    /// The event type, e.g. `"registration"` or `"last changed"`.
+    pub event_action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_actor: Option<String>,
+    /// An RFC 3339 timestamp.
+    pub event_date: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<Link>,
+}
+
+/// Defines an enum of registered string values, parsed case-sensitively.
+macro_rules! registered_values {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $error:ident;
+        $($variant:ident => $value:literal,)+
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
+        pub enum $name {
+            $($variant,)+
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value,)+
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($value => Ok(Self::$variant),)+
+                    other => Err(Error::$error(other.to_string())),
+                }
+            }
+        }
+    };
+}
+
+registered_values! {
+    /// An entity role, from [RFC 9083, Section 10.2.4](https://datatracker.ietf.org/doc/html/rfc9083#section-10.2.4).
+    Role, UnknownRole;
+    Registrant => "registrant",
+    Technical => "technical",
+    Administrative => "administrative",
+    Abuse => "abuse",
+    Billing => "billing",
+    Registrar => "registrar",
+    Reseller => "reseller",
+    Sponsor => "sponsor",
+    Proxy => "proxy",
+    Notifications => "notifications",
+    Noc => "noc",
+}
+
+registered_values! {
+    /// An object status, from [RFC 9083, Section 10.2.2](https://datatracker.ietf.org/doc/html/rfc9083#section-10.2.2)
+    /// and the EPP status mapping of [RFC 8056](https://datatracker.ietf.org/doc/html/rfc8056).
+    Status, UnknownStatus;
+    Validated => "validated",
+    RenewProhibited => "renew prohibited",
+    UpdateProhibited => "update prohibited",
+    TransferProhibited => "transfer prohibited",
+    DeleteProhibited => "delete prohibited",
+    Proxy => "proxy",
+    Private => "private",
+    Removed => "removed",
+    Obscured => "obscured",
+    Associated => "associated",
+    Active => "active",
+    Inactive => "inactive",
+    Locked => "locked",
+    PendingCreate => "pending create",
+    PendingRenew => "pending renew",
+    PendingTransfer => "pending transfer",
+    PendingUpdate => "pending update",
+    PendingDelete => "pending delete",
+    AddPeriod => "add period",
+    AutoRenewPeriod => "auto renew period",
+    ClientDeleteProhibited => "client delete prohibited",
+    ClientHold => "client hold",
+    ClientRenewProhibited => "client renew prohibited",
+    ClientTransferProhibited => "client transfer prohibited",
+    ClientUpdateProhibited => "client update prohibited",
+    PendingRestore => "pending restore",
+    RedemptionPeriod => "redemption period",
+    RenewPeriod => "renew period",
+    ServerDeleteProhibited => "server delete prohibited",
+    ServerRenewProhibited => "server renew prohibited",
+    ServerTransferProhibited => "server transfer prohibited",
+    ServerUpdateProhibited => "server update prohibited",
+    ServerHold => "server hold",
+    TransferPeriod => "transfer period",
+}
@@ -0,0 +1,253 @@
+//! Conformance of entity vCards to the ICANN [gTLD RDAP Response Profile](https://www.icann.org/gtld-rdap-profile)
+//!
+//! The profile requires specific jCard content depending on the role of the entity:
+//!
+//! - 2.4.1: the registrar entity has an `fn`.
+//! - 2.4.5: the registrar abuse contact has an `fn`, a `tel` and an `email`.
+//! - 2.7.3: registrant, administrative, technical and billing contacts have an `fn`, an `adr`, a `tel` and an
+//!   `email`.
+//!
+//! Every `adr`, `tel` and `email` property of those entities must also be well-formed: an `adr` is structured with 7
+//! components and has a `cc` parameter ([RFC 8605](https://datatracker.ietf.org/doc/html/rfc8605)) with an ISO 3166
+//! alpha-2 country code, a `tel` is a `tel:` URI with a `type` parameter and an `email` is a text address. Entities
+//! with other roles have no requirements. Redacted properties are exempt, see [`check`].
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use vicardi::rdap::{icann, redaction::RedactionPolicy, Role};
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new_fn("Abuse desk", None));
+//! vcard.push(Property::new("tel", parameters! {"type" => "voice"}, ValueType::Uri, "tel:+998-71-123-45-67"));
+//! vcard.push(Property::new("email", None, ValueType::Text, "abuse@example.uz"));
+//! assert_eq!(icann::check(&vcard, Role::Abuse, &[], "$"), Ok(()));
+//!
+//! let Err(violations) = icann::check(&vcard, Role::Registrant, &[], "$") else {
+//!     panic!("expected violations");
+//! };
+//! assert_eq!(violations[0].to_string(), "section 2.7.3: missing required adr property");
+//!
+//! vcard.properties[1] = Property::new("tel", None, ValueType::Text, "+998-71-123-45-67");
+//! let Err(violations) = icann::check(&vcard, Role::Abuse, &[], "$") else {
+//!     panic!("expected violations");
+//! };
+//! assert_eq!(
+//!     violations.iter().map(ToString::to_string).collect::<Vec<_>>(),
+//!     [
+//!         r#"section 2.4.5, property 1 ("tel"): the value is not a tel: URI"#,
+//!         r#"section 2.4.5, property 1 ("tel"): missing type parameter"#,
+//!     ]
+//! );
+//!
+//! // Redacted contact data conforms
+//! let policy: RedactionPolicy = serde_json::from_value(json!([
+//!     {"property": "tel", "method": "removal", "name": "Registrant Phone"},
+//!     {"property": "email", "method": "replacementValue", "value": "https://example.uz/contact", "name": "Registrant Email"}
+//! ]))?;
+//! let mut registrant = Vcard::default();
+//! registrant.push(Property::new_fn("Ivan Petrov", None));
+//! registrant.push(Property::new_adr(
+//!     ["".into(), "".into(), "Amir Temur 4".into(), "Tashkent".into(), "".into(), "100000".into(), "".into()].into(),
+//!     parameters! {"cc" => "UZ"},
+//! ));
+//! registrant.push(Property::new("tel", parameters! {"type" => "voice"}, ValueType::Uri, "tel:+998-71-123-45-67"));
+//! registrant.push(Property::new_email("ivan@example.uz", None));
+//! let path = "$.entities[0].vcardArray";
+//! let redacted = registrant.redact(&policy, path);
+//! assert_eq!(icann::check(&registrant, Role::Registrant, &redacted, path), Ok(()));
+//! assert!(icann::check(&registrant, Role::Registrant, &[], "$").is_err());
+//!
+//! // So does a contact form in place of the email
+//! registrant.properties.retain(|property| property.name != "email");
+//! registrant.push(Property::new("contact-uri", None, ValueType::Uri, "https://example.uz/contact"));
+//! assert_eq!(icann::check(&registrant, Role::Registrant, &redacted, path), Ok(()));
+//!
+//! // Redactions of another entity, or of fax numbers only, do not excuse a missing phone
+//! assert!(icann::check(&registrant, Role::Registrant, &redacted, "$.entities[1].vcardArray").is_err());
+//! let policy: RedactionPolicy = serde_json::from_value(json!([
+//!     {"property": "tel", "type": "fax", "method": "removal", "name": "Registrant Fax"}
+//! ]))?;
+//! let mut technical = registrant.clone();
+//! technical.push(Property::new("tel", parameters! {"type" => "fax"}, ValueType::Uri, "tel:+998-71-123-45-68"));
+//! let redacted = technical.redact(&policy, path);
+//! assert_eq!(
+//!     icann::check(&technical, Role::Technical, &redacted, path).unwrap_err()[0].to_string(),
+//!     "section 2.7.3: missing required tel property"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt::Display;
+
+use thiserror::Error;
+
+use super::{
+    redaction::{selected_property, Redacted},
+    Role,
+};
+use crate::{Property, PropertyName, PropertyValue, ValueType, Vcard};
+
+/// A rule of the profile that a vCard breaks.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub struct ProfileViolation {
+    /// The section of the profile the rule comes from, e.g. `"2.4.5"`.
+    pub section: &'static str,
+    /// The index in [`Vcard::properties`] and the name of the offending property, if there is one.
+    pub property: Option<(usize, String)>,
+    pub reason: String,
+}
+
+impl Display for ProfileViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.property {
+            Some((index, name)) => write!(
+                f,
+                "section {}, property {index} ({name:?}): {}",
+                self.section, self.reason
+            ),
+            None => write!(f, "section {}: {}", self.section, self.reason),
+        }
+    }
+}
+
+/// Checks the vCard of an entity with the role against the profile, returning every violation.
+///
+/// `redacted` holds the RFC 9537 `redacted` members of the response, e.g. from [`Vcard::redact`], and `path` is the
+/// JSONPath of the `vcardArray` of the entity, as passed to [`Vcard::redact`]; members with paths outside of it are
+/// ignored. The profile allows redacting contact data (section 2.7.4), so a required property that was removed is
+/// not missing, and the value of a property that was emptied or replaced is not checked. A member limited to a
+/// `type` only covers the properties with that type, and only covers a missing property if it is a `voice` `tel`.
+/// An `email` may also be replaced by a `contact-uri` property
+/// ([RFC 8605](https://datatracker.ietf.org/doc/html/rfc8605)) pointing to a contact form.
+pub fn check(
+    vcard: &Vcard,
+    role: Role,
+    redacted: &[Redacted],
+    path: &str,
+) -> Result<(), Vec<ProfileViolation>> {
+    let Some((section, required)) = requirements(role) else {
+        return Ok(());
+    };
+
+    let selected = |redacted_path: Option<&String>| {
+        redacted_path.and_then(|redacted_path| selected_property(redacted_path, path))
+    };
+    let removed = |name: &PropertyName| {
+        redacted
+            .iter()
+            .filter_map(|r| selected(r.pre_path.as_ref()))
+            .any(|(selected, type_)| {
+                selected == name.as_str()
+                    && type_.is_none_or(|type_| *name == PropertyName::Tel && type_ == "voice")
+            })
+    };
+    let altered = |property: &Property| {
+        redacted
+            .iter()
+            .filter_map(|r| selected(r.post_path.as_ref()))
+            .any(|(selected, type_)| {
+                selected == property.property_name().as_str()
+                    && type_.is_none_or(|type_| {
+                        property
+                            .parameters
+                            .types()
+                            .any(|t| t.as_ref().eq_ignore_ascii_case(&type_))
+                    })
+            })
+    };
+    let present = |name: &PropertyName| {
+        vcard.properties.iter().any(|p| p.property_name() == *name)
+            || (*name == PropertyName::Email
+                && vcard
+                    .properties
+                    .iter()
+                    .any(|p| p.name.eq_ignore_ascii_case("contact-uri")))
+    };
+
+    let mut violations: Vec<_> = required
+        .iter()
+        .filter(|name| !present(name) && !removed(name))
+        .map(|name| ProfileViolation {
+            section,
+            property: None,
+            reason: format!("missing required {name} property"),
+        })
+        .collect();
+
+    for (index, property) in vcard.properties.iter().enumerate() {
+        let check_value = !altered(property);
+        violations.extend(
+            property_violations(property, check_value)
+                .into_iter()
+                .map(|reason| ProfileViolation {
+                    section,
+                    property: Some((index, property.name.clone())),
+                    reason,
+                }),
+        );
+    }
+
+    match violations.is_empty() {
+        true => Ok(()),
+        false => Err(violations),
+    }
+}
+
+/// The section of the profile and the properties required for the role, if the profile covers it.
+fn requirements(role: Role) -> Option<(&'static str, &'static [PropertyName])> {
+    use PropertyName::{Adr, Email, Fn, Tel};
+
+    match role {
+        Role::Registrar => Some(("2.4.1", &[Fn])),
+        Role::Abuse => Some(("2.4.5", &[Fn, Tel, Email])),
+        Role::Registrant | Role::Administrative | Role::Technical | Role::Billing => {
+            Some(("2.7.3", &[Fn, Adr, Tel, Email]))
+        }
+        _ => None,
+    }
+}
+
+/// The reasons an `adr`, `tel` or `email` property is malformed. The value is only checked if `check_value` is set.
+fn property_violations(property: &Property, check_value: bool) -> Vec<String> {
+    let mut reasons = Vec::new();
+
+    match property.property_name() {
+        PropertyName::Adr => {
+            match property.values.as_slice() {
+                [PropertyValue::Structured(components)] if components.len() == 7 => {}
+                _ if !check_value => {}
+                _ => reasons.push("the value is not a structured address of 7 components".into()),
+            }
+            match property.parameters.get("cc").map(Vec::as_slice) {
+                Some([cc]) if cc.len() == 2 && cc.chars().all(|c| c.is_ascii_uppercase()) => {}
+                Some(_) => reasons.push("the cc parameter is not an ISO 3166 alpha-2 code".into()),
+                None => reasons.push("missing cc parameter".into()),
+            }
+        }
+        PropertyName::Tel => {
+            let is_uri = matches!(
+                property.values.as_slice(),
+                [PropertyValue::String(tel)] if tel.starts_with("tel:")
+            );
+            if check_value && (property.value_type != ValueType::Uri || !is_uri) {
+                reasons.push("the value is not a tel: URI".into());
+            }
+            if property.parameters.types().next().is_none() {
+                reasons.push("missing type parameter".into());
+            }
+        }
+        PropertyName::Email => {
+            let is_address = matches!(
+                property.values.as_slice(),
+                [PropertyValue::String(email)] if email.contains('@')
+            );
+            if check_value && (property.value_type != ValueType::Text || !is_address) {
+                reasons.push("the value is not a text email address".into());
+            }
+        }
+        _ => {}
+    }
+
+    reasons
+}
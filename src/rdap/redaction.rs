@@ -0,0 +1,546 @@
+//! [RFC 9537](https://datatracker.ietf.org/doc/html/rfc9537) redaction of jCard properties
+//!
+//! A [`RedactionPolicy`] is a list of rules, each selecting properties by name and optionally by `TYPE` parameter and
+//! applying one of the RFC 9537 redaction methods to them:
+//!
+//! - `removal`: the properties are removed.
+//! - `emptyValue`: the values are replaced with empty strings, component by component for structured values.
+//! - `partialValue`: the listed components of structured values are emptied, other values are emptied entirely and
+//!   described by the path of the whole value.
+//! - `replacementValue`: the values are replaced with a single value, e.g. the URI of a contact form.
+//!
+//! [`Vcard::redact`] applies a policy and returns the [`Redacted`] members describing it, with JSONPath expressions
+//! ([RFC 9535](https://datatracker.ietf.org/doc/html/rfc9535)) relative to the path of the `vcardArray` in the RDAP
+//! response. They belong in the `redacted` member of the top-most object, along with `"redacted"` in its
+//! `rdapConformance`.
+//!
+//! Policies are usually loaded per [`Audience`] from a [`RedactionConfig`]:
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use vicardi::rdap::redaction::*;
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let config: RedactionConfig = serde_json::from_value(json!({
+//!     "anonymous": [
+//!         {"property": "fn", "method": "emptyValue", "name": "Registrant Name"},
+//!         {"property": "tel", "type": "fax", "method": "removal", "name": "Registrant Fax"},
+//!         {"property": "adr", "method": "partialValue", "components": [0, 1, 2], "name": "Registrant Street"},
+//!         {
+//!             "property": "email",
+//!             "method": "replacementValue",
+//!             "value": "https://example.uz/contact",
+//!             "name": "Registrant Email",
+//!             "reason": "Server policy"
+//!         }
+//!     ],
+//!     "law_enforcement": []
+//! }))?;
+//!
+//! let error = serde_json::from_value::<RedactionPolicy>(json!([
+//!     {"property": "email", "method": "partialValue", "components": [0], "name": "Registrant Email"}
+//! ]));
+//! assert!(error.unwrap_err().to_string().contains("partialValue needs a property with a structured value"));
+//! let error = serde_json::from_value::<RedactionPolicy>(json!([
+//!     {"property": "adr", "method": "partialValue", "components": [], "name": "Registrant Street"}
+//! ]));
+//! assert!(error.unwrap_err().to_string().contains("partialValue needs at least one component"));
+//! let error = serde_json::from_value::<RedactionPolicy>(json!([
+//!     {"property": "adr", "method": "partialValue", "components": [7], "name": "Registrant Street"}
+//! ]));
+//! assert!(error.unwrap_err().to_string().contains("partialValue component is out of range"));
+//!
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new_fn("Ivan Petrov", None));
+//! vcard.push(Property::new("tel", parameters! {"type" => "fax"}, ValueType::Uri, "tel:+998-71-123-45-68"));
+//! vcard.push(Property::new("tel", parameters! {"type" => ["voice", "fax"]}, ValueType::Uri, "tel:+998-71-123-45-69"));
+//! vcard.push(Property::new_adr(
+//!     ["".into(), "".into(), "Amir Temur 4".into(), "Tashkent".into(), "".into(), "100000".into(), "".into()].into(),
+//!     parameters! {"cc" => "UZ"},
+//! ));
+//! vcard.push(Property::new_email("ivan@example.uz", None));
+//!
+//! let mut registrant = vcard.clone();
+//! let path = "$.entities[?(@.roles[0]=='registrant')].vcardArray";
+//! assert!(registrant.redact(config.policy(Audience::LawEnforcement), path).is_empty());
+//! assert_eq!(registrant, vcard);
+//!
+//! let redacted = registrant.redact(config.policy(Audience::Anonymous), path);
+//! assert_eq!(
+//!     serde_json::to_value(&registrant)?,
+//!     json!(["vcard", [
+//!         ["version", {}, "text", "4.0"],
+//!         ["fn", {}, "text", ""],
+//!         ["adr", {"cc": "UZ"}, "text", ["", "", "", "Tashkent", "", "100000", ""]],
+//!         ["email", {}, "text", "https://example.uz/contact"]
+//!     ]])
+//! );
+//! assert_eq!(
+//!     serde_json::to_value(&redacted)?,
+//!     json!([
+//!         {
+//!             "name": {"type": "Registrant Name"},
+//!             "postPath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='fn')][3]",
+//!             "pathLang": "jsonpath",
+//!             "method": "emptyValue"
+//!         },
+//!         {
+//!             "name": {"type": "Registrant Fax"},
+//!             "prePath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='tel' && (@[1].type=='fax' || @[1].type[?@=='fax']))]",
+//!             "pathLang": "jsonpath",
+//!             "method": "removal"
+//!         },
+//!         {
+//!             "name": {"type": "Registrant Street"},
+//!             "postPath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='adr')][3][:3]",
+//!             "pathLang": "jsonpath",
+//!             "method": "partialValue"
+//!         },
+//!         {
+//!             "name": {"type": "Registrant Email"},
+//!             "postPath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='email')][3]",
+//!             "pathLang": "jsonpath",
+//!             "method": "replacementValue",
+//!             "reason": {"type": "Server policy"}
+//!         }
+//!     ])
+//! );
+//!
+//! // A string value is emptied entirely
+//! let policy: RedactionPolicy = serde_json::from_value(json!([
+//!     {"property": "org", "method": "partialValue", "components": [1], "name": "Registrant Organization"}
+//! ]))?;
+//! let mut organization = Vcard::default();
+//! organization.push(Property::new_org("Example LLC", None));
+//! let redacted = organization.redact(&policy, "$");
+//! assert_eq!(redacted[0].post_path.as_deref(), Some("$[1][?(@[0]=='org')][3]"));
+//! assert_eq!(organization.properties[0], Property::new_org("", None));
+//!
+//! // Types are compared in lowercase, string literals are escaped and every value of a property is described
+//! let policy: RedactionPolicy = serde_json::from_value(json!([
+//!     {"property": "tel", "type": "FAX", "method": "emptyValue", "name": "Registrant Fax"},
+//!     {"property": "x-note", "type": "it's", "method": "removal", "name": "Registrant Note"}
+//! ]))?;
+//! let mut contact = Vcard::default();
+//! contact.push(Property::new_multivalued("tel", parameters! {"type" => "Fax"}, ValueType::Uri, vec!["tel:+1".into(), "tel:+2".into()]));
+//! contact.push(Property::new("x-note", parameters! {"type" => "it's"}, ValueType::Text, "note"));
+//! let redacted = contact.redact(&policy, "$");
+//! assert_eq!(
+//!     redacted[0].post_path.as_deref(),
+//!     Some("$[1][?(@[0]=='tel' && (@[1].type=='fax' || @[1].type[?@=='fax']))][3:]")
+//! );
+//! assert_eq!(contact.properties[0].parameters.get("type"), Some(&vec!["fax".to_string()]));
+//! assert_eq!(
+//!     redacted[1].pre_path.as_deref(),
+//!     Some(r"$[1][?(@[0]=='x-note' && (@[1].type=='it\'s' || @[1].type[?@=='it\'s']))]")
+//! );
+//! # Ok(())
+//! # }
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Property, PropertyName, PropertyValue, Vcard};
+
+/// The recipient of an RDAP response, who may see more or less of the contact data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Audience {
+    Anonymous,
+    Registrar,
+    LawEnforcement,
+}
+
+/// The redaction policy of each [`Audience`]. Missing audiences have an empty policy.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    #[serde(default)]
+    pub anonymous: RedactionPolicy,
+    #[serde(default)]
+    pub registrar: RedactionPolicy,
+    #[serde(default)]
+    pub law_enforcement: RedactionPolicy,
+}
+
+impl RedactionConfig {
+    pub fn policy(&self, audience: Audience) -> &RedactionPolicy {
+        match audience {
+            Audience::Anonymous => &self.anonymous,
+            Audience::Registrar => &self.registrar,
+            Audience::LawEnforcement => &self.law_enforcement,
+        }
+    }
+}
+
+/// The rules to apply to a vCard, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RedactionPolicy {
+    pub rules: Vec<RedactionRule>,
+}
+
+/// A rule of a [`RedactionPolicy`].
+///
+/// Deserializing a `partialValue` rule fails when its `components` list is empty, its property is registered
+/// without a structured value, i.e. is not `n`, `adr`, `org`, `gender`, `clientpidmap` or an extension property, or
+/// a component is out of range for `n` (5 components), `adr` (7), `gender` (2) or `clientpidmap` (2).
+///
+/// The `type` is lowercased when deserialized, as the JSONPath comparison of the [`Redacted`] paths is
+/// case-sensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedRule")]
+pub struct RedactionRule {
+    pub property: PropertyName,
+    /// Only redact the properties with this `TYPE` parameter value, e.g. `fax`.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    #[serde(flatten)]
+    pub method: RedactionMethod,
+    /// The registered name of the redacted field, e.g. `Registrant Phone`.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// The fields of a [`RedactionRule`], before the method is checked against the property.
+#[derive(Deserialize)]
+struct UncheckedRule {
+    property: PropertyName,
+    #[serde(rename = "type", default)]
+    type_: Option<String>,
+    #[serde(flatten)]
+    method: RedactionMethod,
+    name: String,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl TryFrom<UncheckedRule> for RedactionRule {
+    type Error = Error;
+
+    fn try_from(rule: UncheckedRule) -> Result<Self, Self::Error> {
+        if let RedactionMethod::PartialValue { components } = &rule.method {
+            let invalid = |reason| Error::InvalidRedactionRule {
+                name: rule.name.clone(),
+                reason,
+            };
+            if components.is_empty() {
+                return Err(invalid("partialValue needs at least one component"));
+            }
+            let count = match rule.property {
+                PropertyName::N => 5,
+                PropertyName::Adr => 7,
+                PropertyName::Gender | PropertyName::ClientPidMap => 2,
+                PropertyName::Org | PropertyName::Extension(_) => usize::MAX,
+                _ => {
+                    return Err(invalid(
+                        "partialValue needs a property with a structured value",
+                    ))
+                }
+            };
+            if components.iter().any(|&component| component >= count) {
+                return Err(invalid("partialValue component is out of range"));
+            }
+        }
+
+        Ok(Self {
+            property: rule.property,
+            type_: rule.type_.map(|type_| type_.to_lowercase()),
+            method: rule.method,
+            name: rule.name,
+            reason: rule.reason,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+pub enum RedactionMethod {
+    Removal,
+    EmptyValue,
+    /// Empties the components of structured values at these indices.
+    PartialValue {
+        components: Vec<usize>,
+    },
+    ReplacementValue {
+        value: String,
+    },
+}
+
+impl RedactionMethod {
+    /// The RFC 9537 name of the method.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Removal => "removal",
+            Self::EmptyValue => "emptyValue",
+            Self::PartialValue { .. } => "partialValue",
+            Self::ReplacementValue { .. } => "replacementValue",
+        }
+    }
+}
+
+/// An RFC 9537 `redacted` member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Redacted {
+    pub name: RedactionLabel,
+    /// The path of the redacted field in the unredacted response, used with the `removal` method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_path: Option<String>,
+    /// The path of the redacted field in the redacted response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_lang: Option<String>,
+    pub method: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<RedactionLabel>,
+}
+
+/// The `name` or `reason` of a [`Redacted`] member, either a registered type or a free-form description.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RedactionLabel {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl RedactionLabel {
+    fn of_type(type_: &str) -> Self {
+        Self {
+            type_: Some(type_.to_string()),
+            description: None,
+        }
+    }
+}
+
+impl RedactionRule {
+    fn matches(&self, property: &Property) -> bool {
+        property.property_name() == self.property
+            && self.type_.as_deref().is_none_or(|type_| {
+                property
+                    .parameters
+                    .types()
+                    .any(|t| t.as_ref().eq_ignore_ascii_case(type_))
+            })
+    }
+
+    /// The JSONPath of the matching properties in the properties array at `path`.
+    ///
+    /// The `type` parameter is either a string or an array of strings, which the RFC 9535 filter matches with a
+    /// nested filter query, e.g. `[?(@[0]=='tel' && (@[1].type=='fax' || @[1].type[?@=='fax']))]`.
+    fn path(&self, path: &str) -> String {
+        let name = string_literal(&self.property.as_str().to_lowercase());
+        match &self.type_ {
+            Some(type_) => {
+                let type_ = string_literal(type_);
+                format!(
+                    "{path}[1][?(@[0]=={name} && (@[1].type=={type_} || @[1].type[?@=={type_}]))]"
+                )
+            }
+            None => format!("{path}[1][?(@[0]=={name})]"),
+        }
+    }
+
+    /// The [`Redacted`] member describing the rule.
+    fn redacted(&self, pre_path: Option<String>, post_path: Option<String>) -> Redacted {
+        Redacted {
+            name: RedactionLabel::of_type(&self.name),
+            pre_path,
+            post_path,
+            path_lang: Some("jsonpath".into()),
+            method: self.method.as_str().into(),
+            reason: self.reason.as_deref().map(RedactionLabel::of_type),
+        }
+    }
+
+    fn apply(&self, property: &mut Property) {
+        match &self.method {
+            RedactionMethod::Removal => {}
+            RedactionMethod::EmptyValue => property.values.iter_mut().for_each(empty),
+            RedactionMethod::PartialValue { components } => {
+                for value in &mut property.values {
+                    match value {
+                        PropertyValue::Structured(values) => values
+                            .iter_mut()
+                            .enumerate()
+                            .filter(|(i, _)| components.contains(i))
+                            .for_each(|(_, component)| empty(component)),
+                        value => empty(value),
+                    }
+                }
+            }
+            RedactionMethod::ReplacementValue { value } => {
+                property.values = vec![value.as_str().into()];
+            }
+        }
+    }
+}
+
+/// Lowercases the values of the `type` parameter, so that the case-sensitive JSONPath of a rule with a `type`
+/// selects the property. `TYPE` values are case-insensitive.
+fn normalize_types(property: &mut Property) {
+    if let Some(types) = property.parameters.get_mut("type") {
+        types.iter_mut().for_each(|t| *t = t.to_lowercase());
+    }
+}
+
+/// A single-quoted RFC 9535 string literal, escaping quotes, backslashes and control characters.
+fn string_literal(value: &str) -> String {
+    let mut literal = String::from("'");
+    for c in value.chars() {
+        match c {
+            '\'' => literal.push_str("\\'"),
+            '\\' => literal.push_str("\\\\"),
+            '\u{8}' => literal.push_str("\\b"),
+            '\u{c}' => literal.push_str("\\f"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c <= '\u{1f}' => literal.push_str(&format!("\\u{:04x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+/// The property name and `type` selected by a path of a [`Redacted`] member, if it is a path of a rule under
+/// `path`, as written by [`RedactionRule::path`].
+pub(super) fn selected_property(
+    redacted_path: &str,
+    path: &str,
+) -> Option<(String, Option<String>)> {
+    let rest = redacted_path
+        .strip_prefix(path)?
+        .strip_prefix("[1][?(@[0]==")?;
+    let (name, rest) = parse_string_literal(rest)?;
+    if rest.starts_with(")]") {
+        return Some((name, None));
+    }
+
+    let (type_, rest) = parse_string_literal(rest.strip_prefix(" && (@[1].type==")?)?;
+    rest.starts_with(" || @[1].type[?@==")
+        .then_some((name, Some(type_)))
+}
+
+/// Reads a single-quoted RFC 9535 string literal, returning its value and the rest of the input.
+fn parse_string_literal(input: &str) -> Option<(String, &str)> {
+    let literal = input.strip_prefix('\'')?;
+    let mut chars = literal.char_indices();
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        value.push(match c {
+            '\'' => return Some((value, &literal[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                    char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+                }
+                escaped => escaped,
+            },
+            c => c,
+        });
+    }
+    None
+}
+
+/// Replaces a value with an empty string, keeping the number of components of a structured value.
+fn empty(value: &mut PropertyValue) {
+    match value {
+        PropertyValue::Structured(components) => components.iter_mut().for_each(empty),
+        value => *value = PropertyValue::String(String::new()),
+    }
+}
+
+/// The JSONPath slice of the components, e.g. `[:3]` for 0, 1 and 2, or a union such as `[0,2]`.
+fn components_path(components: &[usize]) -> String {
+    let mut sorted = components.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    match sorted.as_slice() {
+        [first, .., last] if last - first + 1 == sorted.len() => match first {
+            0 => format!("[:{}]", last + 1),
+            first => format!("[{first}:{}]", last + 1),
+        },
+        _ => format!(
+            "[{}]",
+            sorted
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+    }
+}
+
+impl Vcard {
+    /// Applies the policy to the vCard and returns a [`Redacted`] member for each rule that matched a property.
+    ///
+    /// `path` is the JSONPath of the `vcardArray` in the RDAP response, e.g.
+    /// `$.entities[?(@.roles[0]=='registrant')].vcardArray`.
+    pub fn redact(&mut self, policy: &RedactionPolicy, path: &str) -> Vec<Redacted> {
+        let mut redacted = Vec::new();
+
+        for rule in &policy.rules {
+            if !self
+                .properties
+                .iter()
+                .any(|property| rule.matches(property))
+            {
+                continue;
+            }
+
+            let path = rule.path(path);
+            if rule.type_.is_some() {
+                self.properties
+                    .iter_mut()
+                    .filter(|property| rule.matches(property))
+                    .for_each(normalize_types);
+            }
+            if let RedactionMethod::Removal = rule.method {
+                self.properties.retain(|property| !rule.matches(property));
+                redacted.push(rule.redacted(Some(path), None));
+                continue;
+            }
+
+            let structured = self
+                .properties
+                .iter()
+                .filter(|property| rule.matches(property))
+                .flat_map(|property| &property.values)
+                .all(|value| matches!(value, PropertyValue::Structured(_)));
+            self.properties
+                .iter_mut()
+                .filter(|property| rule.matches(property))
+                .for_each(|property| rule.apply(property));
+
+            // The values start at index 3 of the property array, a property may have several of them
+            let values = match self
+                .properties
+                .iter()
+                .filter(|property| rule.matches(property))
+                .any(|property| property.values.len() > 1)
+            {
+                true => "[3:]",
+                false => "[3]",
+            };
+            let post_path = match &rule.method {
+                // String values are emptied entirely
+                RedactionMethod::PartialValue { components } if structured => {
+                    format!("{path}{values}{}", components_path(components))
+                }
+                _ => format!("{path}{values}"),
+            };
+
+            redacted.push(rule.redacted(None, Some(post_path)));
+        }
+
+        redacted
+    }
+}
@@ -0,0 +1,499 @@
+//! A compiled-in copy of the [IANA vCard Elements registry](https://www.iana.org/assignments/vcard-elements/vcard-elements.xhtml)
+//!
+//! The registry lists the properties, parameters and value types defined by
+//! [RFC 6350](https://datatracker.ietf.org/doc/html/rfc6350) and its extensions, together with the parameters and
+//! value types each property allows. [`Vcard::validate`](crate::Vcard::validate) checks a vCard against it.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use vicardi::registry;
+//! # fn main() -> anyhow::Result<()> {
+//! let tel = registry::property("tel").unwrap();
+//! assert_eq!(tel.value_types, [ValueType::Text, ValueType::Uri]);
+//! assert!(tel.allows_parameter("pref"));
+//!
+//! let version = registry::property("version").unwrap();
+//! assert!(!version.allows_parameter("pref"));
+//!
+//! assert!(registry::parameter("sort-as").is_some());
+//! assert!(registry::value_type(&ValueType::LanguageTag));
+//! assert!(registry::property("x-karma-points").is_none());
+//! # Ok(())
+//! # }
+//! ```
+use crate::{Cardinality, ValueType};
+
+/// A registered property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertySpec {
+    /// The lowercase property name.
+    pub name: &'static str,
+    /// The legal value types, the default one first.
+    pub value_types: &'static [ValueType],
+    /// The registered parameters the property allows, besides the ones allowed on every property.
+    pub parameters: &'static [&'static str],
+    pub cardinality: Cardinality,
+    /// The RFC that defines the property.
+    pub reference: &'static str,
+}
+
+impl PropertySpec {
+    /// Whether the parameter is allowed on the property. `x-` parameters are always allowed.
+    pub fn allows_parameter(&self, name: &str) -> bool {
+        name.starts_with("x-")
+            || GLOBAL_PARAMETERS.contains(&name)
+            || self.parameters.contains(&name)
+    }
+
+    /// Whether the value type is legal for the property.
+    pub fn allows_value_type(&self, value_type: &ValueType) -> bool {
+        self.value_types.contains(value_type)
+    }
+}
+
+/// A registered parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterSpec {
+    /// The lowercase parameter name.
+    pub name: &'static str,
+    /// The RFC that defines the parameter.
+    pub reference: &'static str,
+}
+
+/// Looks up a registered property by its name, case-insensitively.
+pub fn property(name: &str) -> Option<&'static PropertySpec> {
+    PROPERTIES
+        .iter()
+        .find(|spec| spec.name.eq_ignore_ascii_case(name))
+}
+
+/// Looks up a registered parameter by its name, case-insensitively.
+pub fn parameter(name: &str) -> Option<&'static ParameterSpec> {
+    PARAMETERS
+        .iter()
+        .find(|spec| spec.name.eq_ignore_ascii_case(name))
+}
+
+/// Whether the value type is registered.
+pub fn value_type(value_type: &ValueType) -> bool {
+    !matches!(value_type, ValueType::Unknown | ValueType::Other(_))
+}
+
+/// Parameters that are allowed on every property.
+///
+/// `group` is the jCard representation of a property group, see
+/// [RFC 7095, Section 3.3.1.2](https://datatracker.ietf.org/doc/html/rfc7095#section-3.3.1.2).
+const GLOBAL_PARAMETERS: &[&str] = &[
+    "group",
+    "author",
+    "author-name",
+    "created",
+    "derived",
+    "prop-id",
+];
+
+pub const PARAMETERS: &[ParameterSpec] = &[
+    param("language", "RFC 6350"),
+    param("value", "RFC 6350"),
+    param("pref", "RFC 6350"),
+    param("altid", "RFC 6350"),
+    param("pid", "RFC 6350"),
+    param("type", "RFC 6350"),
+    param("mediatype", "RFC 6350"),
+    param("calscale", "RFC 6350"),
+    param("sort-as", "RFC 6350"),
+    param("geo", "RFC 6350"),
+    param("tz", "RFC 6350"),
+    param("label", "RFC 6350"),
+    param("index", "RFC 6715"),
+    param("level", "RFC 6715"),
+    param("group", "RFC 7095"),
+    param("cc", "RFC 8605"),
+    param("author", "RFC 9554"),
+    param("author-name", "RFC 9554"),
+    param("created", "RFC 9554"),
+    param("derived", "RFC 9554"),
+    param("phonetic", "RFC 9554"),
+    param("prop-id", "RFC 9554"),
+    param("script", "RFC 9554"),
+    param("service-type", "RFC 9554"),
+    param("username", "RFC 9554"),
+    param("jsptr", "RFC 9555"),
+];
+
+const TEXT: &[ValueType] = &[ValueType::Text];
+const URI: &[ValueType] = &[ValueType::Uri];
+const TEXT_URI: &[ValueType] = &[ValueType::Text, ValueType::Uri];
+const URI_TEXT: &[ValueType] = &[ValueType::Uri, ValueType::Text];
+const DATE: &[ValueType] = &[ValueType::DateAndOrTime, ValueType::Text];
+const TIMESTAMP: &[ValueType] = &[ValueType::Timestamp];
+const LANGUAGE_TAG: &[ValueType] = &[ValueType::LanguageTag];
+
+/// The parameters of most properties with a URI value.
+const URI_PARAMETERS: &[&str] = &["value", "pid", "pref", "type", "mediatype", "altid"];
+/// The parameters of most properties with a text value.
+const TEXT_PARAMETERS: &[&str] = &["value", "language", "pid", "pref", "type", "altid"];
+/// The parameters of `EXPERTISE`, `HOBBY` and `INTEREST`.
+const INTEREST_PARAMETERS: &[&str] = &["level", "index", "language", "pref", "altid", "type"];
+
+pub const PROPERTIES: &[PropertySpec] = &[
+    // RFC 6350
+    prop(
+        "source",
+        URI,
+        &["value", "pid", "pref", "altid", "mediatype"],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop("kind", TEXT, &["value"], Cardinality::AtMostOne, "RFC 6350"),
+    prop(
+        "xml",
+        TEXT,
+        &["value", "altid"],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "fn",
+        TEXT,
+        &["value", "type", "language", "altid", "pid", "pref"],
+        Cardinality::AtLeastOne,
+        "RFC 6350",
+    ),
+    prop(
+        "n",
+        TEXT,
+        &[
+            "value", "sort-as", "language", "altid", "phonetic", "script",
+        ],
+        Cardinality::AtMostOne,
+        "RFC 6350",
+    ),
+    prop(
+        "nickname",
+        TEXT,
+        TEXT_PARAMETERS,
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop("photo", URI, URI_PARAMETERS, Cardinality::Any, "RFC 6350"),
+    prop(
+        "bday",
+        DATE,
+        &["value", "altid", "calscale", "language"],
+        Cardinality::AtMostOne,
+        "RFC 6350",
+    ),
+    prop(
+        "anniversary",
+        DATE,
+        &["value", "altid", "calscale", "language"],
+        Cardinality::AtMostOne,
+        "RFC 6350",
+    ),
+    prop(
+        "gender",
+        TEXT,
+        &["value"],
+        Cardinality::AtMostOne,
+        "RFC 6350",
+    ),
+    prop(
+        "adr",
+        TEXT,
+        &[
+            "value", "label", "language", "geo", "tz", "altid", "pid", "pref", "type", "cc",
+            "phonetic", "script",
+        ],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "tel",
+        TEXT_URI,
+        &["value", "type", "pid", "pref", "altid", "mediatype"],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "email",
+        TEXT,
+        &["value", "pid", "pref", "type", "altid"],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "impp",
+        URI,
+        &[
+            "value",
+            "pid",
+            "pref",
+            "type",
+            "mediatype",
+            "altid",
+            "service-type",
+            "username",
+        ],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "lang",
+        LANGUAGE_TAG,
+        &["value", "pid", "pref", "altid", "type"],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "tz",
+        &[ValueType::Text, ValueType::Uri, ValueType::UtcOffset],
+        &["value", "altid", "pid", "pref", "type", "mediatype"],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop("geo", URI, URI_PARAMETERS, Cardinality::Any, "RFC 6350"),
+    prop("title", TEXT, TEXT_PARAMETERS, Cardinality::Any, "RFC 6350"),
+    prop("role", TEXT, TEXT_PARAMETERS, Cardinality::Any, "RFC 6350"),
+    prop(
+        "logo",
+        URI,
+        &[
+            "value",
+            "language",
+            "pid",
+            "pref",
+            "type",
+            "mediatype",
+            "altid",
+        ],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "org",
+        TEXT,
+        &[
+            "value", "sort-as", "language", "pid", "pref", "altid", "type",
+        ],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "member",
+        URI,
+        &["value", "pid", "pref", "altid", "mediatype"],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "related",
+        URI_TEXT,
+        &[
+            "value",
+            "type",
+            "pid",
+            "pref",
+            "altid",
+            "mediatype",
+            "language",
+        ],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "categories",
+        TEXT,
+        &["value", "pid", "pref", "type", "altid"],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop("note", TEXT, TEXT_PARAMETERS, Cardinality::Any, "RFC 6350"),
+    prop(
+        "prodid",
+        TEXT,
+        &["value"],
+        Cardinality::AtMostOne,
+        "RFC 6350",
+    ),
+    prop(
+        "rev",
+        TIMESTAMP,
+        &["value"],
+        Cardinality::AtMostOne,
+        "RFC 6350",
+    ),
+    prop(
+        "sound",
+        URI,
+        &[
+            "value",
+            "language",
+            "pid",
+            "pref",
+            "type",
+            "mediatype",
+            "altid",
+        ],
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop(
+        "uid",
+        URI_TEXT,
+        &["value"],
+        Cardinality::AtMostOne,
+        "RFC 6350",
+    ),
+    prop("clientpidmap", TEXT, &[], Cardinality::Any, "RFC 6350"),
+    prop("url", URI, URI_PARAMETERS, Cardinality::Any, "RFC 6350"),
+    prop("version", TEXT, &[], Cardinality::ExactlyOne, "RFC 6350"),
+    prop(
+        "key",
+        URI_TEXT,
+        URI_PARAMETERS,
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop("fburl", URI, URI_PARAMETERS, Cardinality::Any, "RFC 6350"),
+    prop(
+        "caladruri",
+        URI,
+        URI_PARAMETERS,
+        Cardinality::Any,
+        "RFC 6350",
+    ),
+    prop("caluri", URI, URI_PARAMETERS, Cardinality::Any, "RFC 6350"),
+    // RFC 6474
+    prop(
+        "birthplace",
+        TEXT_URI,
+        &["value", "altid", "language"],
+        Cardinality::AtMostOne,
+        "RFC 6474",
+    ),
+    prop(
+        "deathplace",
+        TEXT_URI,
+        &["value", "altid", "language"],
+        Cardinality::AtMostOne,
+        "RFC 6474",
+    ),
+    prop(
+        "deathdate",
+        DATE,
+        &["value", "altid", "calscale", "language"],
+        Cardinality::AtMostOne,
+        "RFC 6474",
+    ),
+    // RFC 6715
+    prop(
+        "expertise",
+        TEXT,
+        INTEREST_PARAMETERS,
+        Cardinality::Any,
+        "RFC 6715",
+    ),
+    prop(
+        "hobby",
+        TEXT,
+        INTEREST_PARAMETERS,
+        Cardinality::Any,
+        "RFC 6715",
+    ),
+    prop(
+        "interest",
+        TEXT,
+        INTEREST_PARAMETERS,
+        Cardinality::Any,
+        "RFC 6715",
+    ),
+    prop(
+        "org-directory",
+        URI,
+        &["value", "pref", "index", "language", "pid", "altid", "type"],
+        Cardinality::Any,
+        "RFC 6715",
+    ),
+    // RFC 8605
+    prop(
+        "contact-uri",
+        URI,
+        &["value", "pref"],
+        Cardinality::Any,
+        "RFC 8605",
+    ),
+    // RFC 9554
+    prop(
+        "created",
+        TIMESTAMP,
+        &["value"],
+        Cardinality::AtMostOne,
+        "RFC 9554",
+    ),
+    prop(
+        "gramgender",
+        TEXT,
+        &["value", "language"],
+        Cardinality::Any,
+        "RFC 9554",
+    ),
+    prop(
+        "language",
+        LANGUAGE_TAG,
+        &["value"],
+        Cardinality::AtMostOne,
+        "RFC 9554",
+    ),
+    prop(
+        "pronouns",
+        TEXT,
+        &["value", "language", "pref", "type", "altid"],
+        Cardinality::Any,
+        "RFC 9554",
+    ),
+    prop(
+        "socialprofile",
+        URI_TEXT,
+        &[
+            "value",
+            "pid",
+            "pref",
+            "type",
+            "altid",
+            "language",
+            "service-type",
+            "username",
+        ],
+        Cardinality::Any,
+        "RFC 9554",
+    ),
+    // RFC 9555
+    prop(
+        "jsprop",
+        TEXT,
+        &["value", "jsptr"],
+        Cardinality::Any,
+        "RFC 9555",
+    ),
+];
+
+const fn prop(
+    name: &'static str,
+    value_types: &'static [ValueType],
+    parameters: &'static [&'static str],
+    cardinality: Cardinality,
+    reference: &'static str,
+) -> PropertySpec {
+    PropertySpec {
+        name,
+        value_types,
+        parameters,
+        cardinality,
+        reference,
+    }
+}
+
+const fn param(name: &'static str, reference: &'static str) -> ParameterSpec {
+    ParameterSpec { name, reference }
+}
@@ -3,7 +3,10 @@ use serde::{
     Serialize,
 };
 
-use crate::{Parameters, Property, PropertyValue, Vcard};
+use crate::{
+    lossless::{PropertyLayout, ValueLayout, VersionLayout},
+    Error as VcardError, LosslessVcard, Parameters, Property, PropertyValue, Vcard,
+};
 
 impl Serialize for Vcard {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -13,11 +16,8 @@ impl Serialize for Vcard {
         let mut vcard = serializer.serialize_seq(Some(2))?;
         vcard.serialize_element("vcard")?;
 
-        struct VersionPrefix<'a, T>(&'a str, &'a [T]);
-        impl<T> Serialize for VersionPrefix<'_, T>
-        where
-            T: Serialize,
-        {
+        struct VersionPrefix<'a>(&'a str, &'a [Property]);
+        impl Serialize for VersionPrefix<'_> {
             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             where
                 S: serde::Serializer,
@@ -26,9 +26,10 @@ impl Serialize for Vcard {
 
                 seq.serialize_element(&Property::new("version", None, "text", self.0))?;
 
-                self.1
-                    .iter()
-                    .try_for_each(|prop| seq.serialize_element(prop))?;
+                self.1.iter().enumerate().try_for_each(|(i, prop)| {
+                    prop.check(Some(i)).map_err(S::Error::custom)?;
+                    seq.serialize_element(prop)
+                })?;
 
                 seq.end()
             }
@@ -39,47 +40,142 @@ impl Serialize for Vcard {
     }
 }
 
-impl Serialize for Property {
+impl Serialize for LosslessVcard {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        if self.values.is_empty() {
-            return Err(S::Error::custom(
-                "at least one value must be present in a property",
-            ));
+        let mut vcard = serializer.serialize_seq(Some(2))?;
+        vcard.serialize_element("vcard")?;
+        vcard.serialize_element(&LosslessProperties(self))?;
+        vcard.end()
+    }
+}
+
+struct LosslessProperties<'a>(&'a LosslessVcard);
+
+impl Serialize for LosslessProperties<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let LosslessVcard { vcard, layout } = self.0;
+
+        let version = match &layout.version {
+            VersionLayout::Missing if vcard.version.is_empty() => None,
+            VersionLayout::Default | VersionLayout::Missing => Some((
+                0,
+                Property::new("version", None, "text", vcard.version.as_str()),
+                None,
+            )),
+            VersionLayout::Source {
+                index,
+                version,
+                property,
+                layout,
+            } => {
+                let mut property = Property::clone(property);
+                if *version != vcard.version {
+                    property.values = vec![vcard.version.as_str().into()];
+                }
+                Some((*index, property, Some(layout)))
+            }
+        };
+
+        let len = vcard.properties.len() + usize::from(version.is_some());
+        let mut seq = serializer.serialize_seq(Some(len))?;
+
+        let mut version = version
+            .map(|(index, property, layout)| (index.min(vcard.properties.len()), property, layout));
+
+        for (i, property) in vcard.properties.iter().enumerate() {
+            if let Some((_, property, layout)) = version.take_if(|(index, ..)| *index == i) {
+                seq.serialize_element(&WithLayout(&property, layout))?;
+            }
+
+            property.check(Some(i)).map_err(S::Error::custom)?;
+            let layout = layout
+                .properties
+                .get(i)
+                .filter(|layout| layout.name == property.name);
+            seq.serialize_element(&WithLayout(property, layout))?;
+        }
+
+        if let Some((_, property, layout)) = version {
+            seq.serialize_element(&WithLayout(&property, layout))?;
         }
 
-        let mut seq = serializer.serialize_seq(Some(3 + self.values.len()))?;
+        seq.end()
+    }
+}
+
+/// Serializes a value with the layout it was deserialized with, if any.
+struct WithLayout<'a, T, L>(&'a T, Option<&'a L>);
+
+impl Serialize for Property {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        WithLayout(self, None).serialize(serializer)
+    }
+}
+
+impl Serialize for WithLayout<'_, Property, PropertyLayout> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let Self(property, layout) = *self;
+
+        property.check(None).map_err(S::Error::custom)?;
 
-        seq.serialize_element(&self.name)?;
-        seq.serialize_element(&MapToOneOrMany(&self.parameters))?;
-        seq.serialize_element(&self.value_type)?;
-        self.values
-            .iter()
-            .try_for_each(|v| seq.serialize_element(v))?;
+        let mut seq = serializer.serialize_seq(Some(3 + property.values.len()))?;
+
+        seq.serialize_element(&property.name)?;
+        seq.serialize_element(&WithLayout(
+            &property.parameters,
+            layout.map(|layout| &layout.arrays),
+        ))?;
+        seq.serialize_element(&property.value_type)?;
+        property.values.iter().enumerate().try_for_each(|(i, v)| {
+            seq.serialize_element(&WithLayout(
+                v,
+                layout.and_then(|layout| layout.values.get(i)),
+            ))
+        })?;
 
         seq.end()
     }
 }
 
-struct MapToOneOrMany<'a>(&'a Parameters);
+impl Serialize for Parameters {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        WithLayout(self, None).serialize(serializer)
+    }
+}
 
-impl Serialize for MapToOneOrMany<'_> {
+impl Serialize for WithLayout<'_, Parameters, Vec<String>> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        let Self(parameters, arrays) = *self;
+        let is_array = |key: &String| arrays.is_some_and(|arrays| arrays.contains(key));
+
+        let mut map = serializer.serialize_map(Some(parameters.len()))?;
 
-        for (key, value) in self.0 {
+        for (key, value) in parameters {
             match value.as_slice() {
                 [] => {
                     return Err(S::Error::custom(
                         "vcard property parameter is an empty array",
                     ))
                 }
-                [single] => map.serialize_entry(key, single)?,
+                [single] if !is_array(key) => map.serialize_entry(key, single)?,
                 multiple => map.serialize_entry(key, multiple)?,
             }
         }
@@ -90,6 +186,45 @@ impl Serialize for MapToOneOrMany<'_> {
 
 impl Serialize for PropertyValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        WithLayout(self, None).serialize(serializer)
+    }
+}
+
+impl Serialize for WithLayout<'_, PropertyValue, ValueLayout> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let Self(value, layout) = *self;
+
+        match (value, layout) {
+            (PropertyValue::Float(float), Some(ValueLayout::Integer))
+                if float.fract() == 0.0 && (i64::MIN as f64..=i64::MAX as f64).contains(float) =>
+            {
+                serializer.serialize_i64(*float as i64)
+            }
+            (PropertyValue::Integer(int), Some(ValueLayout::Float)) => {
+                serializer.serialize_f64(*int as f64)
+            }
+            (PropertyValue::Structured(property_values), Some(ValueLayout::Array(layouts)))
+                if !property_values.is_empty() =>
+            {
+                let mut seq = serializer.serialize_seq(Some(property_values.len()))?;
+                for (i, value) in property_values.iter().enumerate() {
+                    seq.serialize_element(&WithLayout(value, layouts.get(i)))?
+                }
+                seq.end()
+            }
+            _ => value.serialize_normalized(serializer),
+        }
+    }
+}
+
+impl PropertyValue {
+    fn serialize_normalized<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
@@ -99,7 +234,10 @@ impl Serialize for PropertyValue {
             PropertyValue::Integer(int) => serializer.serialize_i64(*int),
             PropertyValue::Float(float) => serializer.serialize_f64(*float),
             PropertyValue::Structured(property_values) => match property_values.as_slice() {
-                [] => Err(S::Error::custom("empty structured value")),
+                [] => Err(S::Error::custom(VcardError::EmptyStructuredValue {
+                    index: None,
+                    name: None,
+                })),
                 [single] => single.serialize(serializer),
                 many => {
                     let mut seq = serializer.serialize_seq(Some(many.len()))?;
@@ -112,3 +250,39 @@ impl Serialize for PropertyValue {
         }
     }
 }
+
+impl Property {
+    /// Checks that the property can be serialized. `index` is its index in [`Vcard::properties`], if known.
+    fn check(&self, index: Option<usize>) -> Result<(), VcardError> {
+        fn has_empty_structured(value: &PropertyValue) -> bool {
+            match value {
+                PropertyValue::Structured(values) => {
+                    values.is_empty() || values.iter().any(has_empty_structured)
+                }
+                _ => false,
+            }
+        }
+
+        if self.values.is_empty() {
+            return Err(VcardError::MissingValue {
+                index,
+                name: self.name.clone(),
+            });
+        }
+        if let Some((parameter, _)) = self.parameters.iter().find(|(_, values)| values.is_empty()) {
+            return Err(VcardError::EmptyParameter {
+                index,
+                name: self.name.clone(),
+                parameter: parameter.clone(),
+            });
+        }
+        if self.values.iter().any(has_empty_structured) {
+            return Err(VcardError::EmptyStructuredValue {
+                index,
+                name: Some(self.name.clone()),
+            });
+        }
+
+        Ok(())
+    }
+}
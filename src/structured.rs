@@ -1,10 +1,8 @@
 /// Helper types to construct structured properties
 use std::{convert::Infallible, fmt::Display, str::FromStr};
 
+use crate::{Error, Property, PropertyValue, ValueType};
 use serde_with::{DeserializeFromStr, SerializeDisplay};
-use thiserror::Error;
-
-use crate::{Property, PropertyValue};
 
 #[derive(Debug, Clone, Default)]
 pub struct Address {
@@ -61,14 +59,15 @@ impl From<[String; 7]> for Address {
 }
 
 impl TryFrom<Vec<String>> for Address {
-    type Error = InvalidStructuredAddress;
+    type Error = Error;
 
     fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
+        let components = value.len();
         let Ok(
             [post_office_box, extended_address, street_address, locality, region, postal_code, country],
         ) = TryInto::<[String; 7]>::try_into(value)
         else {
-            return Err(InvalidStructuredAddress);
+            return Err(Error::InvalidStructuredAddress { components });
         };
 
         Ok(Self {
@@ -83,13 +82,9 @@ impl TryFrom<Vec<String>> for Address {
     }
 }
 
-#[derive(Error, Debug)]
-#[error("Invalid strucutured address")]
-pub struct InvalidStructuredAddress;
-
 impl From<Address> for Property {
     fn from(address: Address) -> Self {
-        Self::new("adr", None, "text", address)
+        Self::new("adr", None, ValueType::Text, address)
     }
 }
 
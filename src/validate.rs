@@ -0,0 +1,206 @@
+//! Validation of vCard properties against RFC 6350 and the IANA registry
+use thiserror::Error;
+
+use crate::{registry, Cardinality, PropertyName, ValueType, Vcard};
+
+/// A problem found by [`Vcard::validate`].
+///
+/// `index` is the index of the property in [`Vcard::properties`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A property appears fewer times than its [`Cardinality`] allows.
+    #[error("missing required {name} property")]
+    Missing { name: PropertyName },
+    /// A property appears more times than its [`Cardinality`] allows.
+    #[error("the {name} property may appear at most once, found {count} instances")]
+    TooMany { name: PropertyName, count: usize },
+    #[error("property {index}: {name:?} is neither registered nor an x- property")]
+    UnregisteredProperty { index: usize, name: String },
+    #[error("property {index} ({property:?}): {parameter:?} is neither a registered nor an x- parameter")]
+    UnregisteredParameter {
+        index: usize,
+        property: String,
+        parameter: String,
+    },
+    #[error("property {index} ({property:?}): the {parameter:?} parameter is not allowed on this property")]
+    IllegalParameter {
+        index: usize,
+        property: String,
+        parameter: String,
+    },
+    #[error("property {index} ({property:?}): the {value_type} value type is not allowed on this property")]
+    IllegalValueType {
+        index: usize,
+        property: String,
+        value_type: ValueType,
+    },
+}
+
+impl Violation {
+    /// A stable, kebab-case identifier of the variant, returned by [`Error::code`](crate::Error::code).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Missing { .. } => "missing-property",
+            Self::TooMany { .. } => "too-many-properties",
+            Self::UnregisteredProperty { .. } => "unregistered-property",
+            Self::UnregisteredParameter { .. } => "unregistered-parameter",
+            Self::IllegalParameter { .. } => "illegal-parameter",
+            Self::IllegalValueType { .. } => "illegal-value-type",
+        }
+    }
+}
+
+impl Vcard {
+    /// Checks the vCard against RFC 6350 and the [`registry`].
+    ///
+    /// - The number of instances of each property must match its [`PropertyName::cardinality`]. [`Vcard::version`]
+    ///   counts as a `version` property if it is not empty, and properties with the same name and `ALTID` parameter
+    ///   count as a single instance.
+    /// - Property and parameter names must be registered or start with `x-`.
+    /// - Registered parameters must be allowed on the property they are used on.
+    /// - Registered properties must have one of their legal value types. Other properties may have any registered or
+    ///   `x-` value type, or `unknown`.
+    ///
+    /// Every problem found is returned in an [`Error::Validation`](crate::Error::Validation).
+    ///
+    /// ```rust
+    /// # use vicardi::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut vcard = Vcard::default();
+    /// assert_eq!(
+    ///     vcard.validate(),
+    ///     Err(Error::Validation(vec![Violation::Missing { name: PropertyName::Fn }]))
+    /// );
+    ///
+    /// vcard.push(Property::new_fn("Ivan Petrov", parameters! {"altid" => "1"}));
+    /// vcard.push(Property::new_fn("Иван Петров", parameters! {"altid" => "1", "language" => "ru"}));
+    /// vcard.push(Property::new("bday", parameters! {"altid" => "1"}, ValueType::DateAndOrTime, "--04-12"));
+    /// vcard.push(Property::new("bday", parameters! {"altid" => "2"}, ValueType::Text, "in April"));
+    /// assert_eq!(
+    ///     vcard.validate(),
+    ///     Err(Error::Validation(vec![Violation::TooMany { name: PropertyName::Bday, count: 2 }]))
+    /// );
+    ///
+    /// vcard.properties.pop();
+    /// assert_eq!(vcard.validate(), Ok(()));
+    ///
+    /// vcard.push(Property::new("tel", parameters! {"mediatype" => "text/plain", "x-ablabel" => "work"}, ValueType::Uri, "tel:+1"));
+    /// vcard.push(Property::new("version", parameters! {"pref" => "1"}, ValueType::Text, "4.0"));
+    /// vcard.push(Property::new("birthday", None, ValueType::Date, "1985-04-12"));
+    /// let Err(Error::Validation(violations)) = vcard.validate() else {
+    ///     panic!("expected violations");
+    /// };
+    /// assert_eq!(
+    ///     violations.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    ///     [
+    ///         "the version property may appear at most once, found 2 instances",
+    ///         r#"property 4 ("version"): the "pref" parameter is not allowed on this property"#,
+    ///         r#"property 5: "birthday" is neither registered nor an x- property"#,
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<(), crate::Error> {
+        let mut violations = self.cardinality_violations();
+        for (index, property) in self.properties.iter().enumerate() {
+            violations.extend(property_violations(index, property));
+        }
+
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(crate::Error::Validation(violations)),
+        }
+    }
+
+    fn cardinality_violations(&self) -> Vec<Violation> {
+        // The number of instances of each property, in the order they first appear
+        let mut instances: Vec<(PropertyName, Vec<Option<&str>>)> = vec![
+            (PropertyName::Version, Vec::new()),
+            (PropertyName::Fn, Vec::new()),
+        ];
+        if !self.version.is_empty() {
+            instances[0].1.push(None);
+        }
+
+        for property in &self.properties {
+            let name = property.property_name();
+            let index = match instances.iter().position(|(known, _)| *known == name) {
+                Some(index) => index,
+                None => {
+                    instances.push((name, Vec::new()));
+                    instances.len() - 1
+                }
+            };
+
+            let altid = property.parameters.altid();
+            let altids = &mut instances[index].1;
+            if altid.is_none() || !altids.contains(&altid) {
+                altids.push(altid);
+            }
+        }
+
+        instances
+            .into_iter()
+            .filter_map(|(name, altids)| {
+                let count = altids.len();
+                match name.cardinality() {
+                    cardinality if cardinality.allows(count) => None,
+                    Cardinality::ExactlyOne | Cardinality::AtLeastOne if count == 0 => {
+                        Some(Violation::Missing { name })
+                    }
+                    _ => Some(Violation::TooMany { name, count }),
+                }
+            })
+            .collect()
+    }
+}
+
+fn property_violations(index: usize, property: &crate::Property) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let name = property.name.to_lowercase();
+    let spec = registry::property(&name);
+
+    if spec.is_none() && !name.starts_with("x-") {
+        violations.push(Violation::UnregisteredProperty {
+            index,
+            name: property.name.clone(),
+        });
+    }
+
+    for parameter in property.parameters.keys() {
+        let parameter = parameter.to_lowercase();
+        let violation = match (spec, registry::parameter(&parameter)) {
+            _ if parameter.starts_with("x-") => continue,
+            (_, None) => Violation::UnregisteredParameter {
+                index,
+                property: property.name.clone(),
+                parameter,
+            },
+            (Some(spec), Some(_)) if !spec.allows_parameter(&parameter) => {
+                Violation::IllegalParameter {
+                    index,
+                    property: property.name.clone(),
+                    parameter,
+                }
+            }
+            _ => continue,
+        };
+        violations.push(violation);
+    }
+
+    let legal = match (spec, &property.value_type) {
+        (Some(spec), value_type) => spec.allows_value_type(value_type),
+        (None, ValueType::Other(other)) => other.starts_with("x-"),
+        (None, _) => true,
+    };
+    if !legal {
+        violations.push(Violation::IllegalValueType {
+            index,
+            property: property.name.clone(),
+            value_type: property.value_type.clone(),
+        });
+    }
+
+    violations
+}
@@ -0,0 +1,116 @@
+//! The value type of a jCard property
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+/// A [`crate::Property::value_type`], as defined by
+/// [RFC 6350, Section 4](https://datatracker.ietf.org/doc/html/rfc6350#section-4) and
+/// [RFC 7095, Section 5](https://datatracker.ietf.org/doc/html/rfc7095#section-5).
+///
+/// Value types are lowercase in jCard, so only the exact lowercase identifiers are recognized. Anything else, such as
+/// an extension type or a misspelled identifier, is kept verbatim in [`ValueType::Other`] so that it is serialized
+/// back unchanged.
+///
+/// ```rust
+/// # use vicardi::*;
+/// # use serde_json::json;
+/// # fn main() -> anyhow::Result<()> {
+/// let property = Property::new("url", None, ValueType::Uri, "https://example.com");
+/// assert_eq!(serde_json::to_value(&property)?, json!(["url", {}, "uri", "https://example.com"]));
+///
+/// let property: Property = serde_json::from_value(json!(["x-foo", {}, "URI", "bar"]))?;
+/// assert_eq!(property.value_type, ValueType::Other("URI".into()));
+///
+/// assert_eq!("date-and-or-time".parse::<ValueType>()?, ValueType::DateAndOrTime);
+/// assert_eq!(ValueType::from("texts").to_string(), "texts");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
+pub enum ValueType {
+    Text,
+    Uri,
+    Date,
+    Time,
+    DateTime,
+    DateAndOrTime,
+    Timestamp,
+    Boolean,
+    Integer,
+    Float,
+    UtcOffset,
+    LanguageTag,
+    Unknown,
+    /// Any other value type identifier, e.g. `"binary"` from a vCard 3.0 or an extension type.
+    Other(String),
+}
+
+impl ValueType {
+    /// The jCard identifier of the value type, e.g. `"date-time"`.
+    pub fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+}
+
+impl Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl AsRef<str> for ValueType {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Text => "text",
+            Self::Uri => "uri",
+            Self::Date => "date",
+            Self::Time => "time",
+            Self::DateTime => "date-time",
+            Self::DateAndOrTime => "date-and-or-time",
+            Self::Timestamp => "timestamp",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::UtcOffset => "utc-offset",
+            Self::LanguageTag => "language-tag",
+            Self::Unknown => "unknown",
+            Self::Other(other) => other.as_ref(),
+        }
+    }
+}
+
+impl FromStr for ValueType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "text" => Self::Text,
+            "uri" => Self::Uri,
+            "date" => Self::Date,
+            "time" => Self::Time,
+            "date-time" => Self::DateTime,
+            "date-and-or-time" => Self::DateAndOrTime,
+            "timestamp" => Self::Timestamp,
+            "boolean" => Self::Boolean,
+            "integer" => Self::Integer,
+            "float" => Self::Float,
+            "utc-offset" => Self::UtcOffset,
+            "language-tag" => Self::LanguageTag,
+            "unknown" => Self::Unknown,
+            other => Self::Other(other.to_string()),
+        })
+    }
+}
+
+impl From<&str> for ValueType {
+    fn from(value: &str) -> Self {
+        let Ok(value_type) = value.parse();
+        value_type
+    }
+}
+
+impl From<String> for ValueType {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
@@ -0,0 +1,942 @@
+//! Plain-text vCard ([RFC 6350](https://datatracker.ietf.org/doc/html/rfc6350)) reading and writing.
+//!
+//! The text format is mapped onto the same [`Vcard`] model that is used for jCard, following the rules of
+//! [RFC 7095, Section 5](https://datatracker.ietf.org/doc/html/rfc7095#section-5):
+//!
+//! - property and parameter names are lowercased,
+//! - the `VALUE` parameter becomes [`Property::value_type`] (or the property's default value type if it is absent),
+//! - a property group (`item1.TEL`) becomes the `group` parameter,
+//! - structured values (`N`, `ADR`, `ORG`, ...) become [`PropertyValue::Structured`],
+//! - parameter values are unescaped as described in [RFC 6868](https://datatracker.ietf.org/doc/html/rfc6868) (`^n`
+//!   becomes a newline), see [`caret_decode`],
+//! - date and time values are converted between the basic (`19850412`) and extended (`1985-04-12`) formats.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let vcf = "BEGIN:VCARD\r\n\
+//!            VERSION:4.0\r\n\
+//!            FN:John Doe\r\n\
+//!            N:Doe;John;;;\r\n\
+//!            BDAY:--0412\r\n\
+//!            item1.TEL;VALUE=uri;TYPE=work,voice:tel:+1-555-555-5555\r\n\
+//!            CATEGORIES:rust,serde\r\n\
+//!            END:VCARD\r\n";
+//!
+//! let vcard = Vcard::from_vcf(vcf)?;
+//!
+//! let json = json!([
+//!     "vcard",
+//!     [
+//!         ["version", {}, "text", "4.0"],
+//!         ["fn", {}, "text", "John Doe"],
+//!         ["n", {}, "text", ["Doe", "John", "", "", ""]],
+//!         ["bday", {}, "date-and-or-time", "--04-12"],
+//!         ["tel", {"group": "item1", "type": ["work", "voice"]}, "uri", "tel:+1-555-555-5555"],
+//!         ["categories", {}, "text", "rust", "serde"]
+//!     ]
+//! ]);
+//!
+//! assert_eq!(serde_json::to_value(&vcard)?, json);
+//! assert_eq!(Vcard::from_vcf(&vcard.to_vcf())?, vcard);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Legacy versions
+//!
+//! vCard 3.0 ([RFC 2426](https://datatracker.ietf.org/doc/html/rfc2426)) and 2.1 files are read into the same model,
+//! with [`Vcard::version`] set to the version of the source. Only the syntax is converted, see
+//! [`Vcard::upgrade_to_v4`] for the semantic differences between the versions.
+//!
+//! - `ENCODING=QUOTED-PRINTABLE` values are decoded using their `CHARSET` (UTF-8 if absent), and both parameters are
+//!   dropped,
+//! - `ENCODING=b` and `ENCODING=BASE64` values are kept as-is with an `encoding` parameter of `"b"` and a `"binary"`
+//!   value type,
+//! - parameters without a name (`TEL;WORK;VOICE:`) are assigned to `encoding`, `value` or `type` and the type values
+//!   are lowercased,
+//! - `VALUE=URL` becomes the `"uri"` value type,
+//! - vCard 2.1 `AGENT` properties with an embedded vCard keep its text as their value.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # use serde_json::json;
+//! # fn main() -> anyhow::Result<()> {
+//! let vcf = "BEGIN:VCARD\r\n\
+//!            VERSION:2.1\r\n\
+//!            N:Doe;John\r\n\
+//!            TEL;WORK;VOICE:+1-555-555-5555\r\n\
+//!            NOTE;ENCODING=QUOTED-PRINTABLE;CHARSET=UTF-8:Caf=C3=A9 =\r\n\
+//!            au lait\r\n\
+//!            END:VCARD\r\n";
+//!
+//! let vcard = Vcard::from_vcf(vcf)?;
+//!
+//! let json = json!([
+//!     "vcard",
+//!     [
+//!         ["version", {}, "text", "2.1"],
+//!         ["n", {}, "text", ["Doe", "John"]],
+//!         ["tel", {"type": ["work", "voice"]}, "text", "+1-555-555-5555"],
+//!         ["note", {}, "text", "Café au lait"]
+//!     ]
+//! ]);
+//!
+//! assert_eq!(serde_json::to_value(&vcard)?, json);
+//!
+//! let legacy = |note: &str| Vcard::from_vcf(&format!("BEGIN:VCARD\r\nVERSION:2.1\r\n{note}\r\nEND:VCARD\r\n"));
+//! let note = legacy("NOTE;ENCODING=QUOTED-PRINTABLE;CHARSET=ISO-8859-1:Caf=E9")?;
+//! assert_eq!(note.properties[0].values, ["Café".into()]);
+//! let note = legacy("NOTE;QUOTED-PRINTABLE;CHARSET=windows-1252:=80 5, 1=BD")?;
+//! assert_eq!(note.properties[0].values, ["€ 5, 1½".into()]);
+//! assert_eq!(
+//!     legacy("NOTE;ENCODING=QUOTED-PRINTABLE;CHARSET=KOI8-R:=F0").unwrap_err().code(),
+//!     "vcf-unsupported-charset"
+//! );
+//! assert!(legacy("NOTE;ENCODING=QUOTED-PRINTABLE:=FF").is_err());
+//! # Ok(())
+//! # }
+//! ```
+use std::borrow::Cow;
+
+use thiserror::Error;
+
+use crate::{
+    datetime::{datetime_format, is_datetime},
+    is_group,
+    parameters::{caret_decode, caret_encode},
+    Parameters, Property, PropertyName, PropertyValue, ValueType, Vcard,
+};
+
+/// Maximum length of a content line in octets, excluding the line break.
+const MAX_LINE_OCTETS: usize = 75;
+
+/// A text vCard parse error pointing at the offending position in the (folded) input.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("line {line}, column {column}: {kind}")]
+pub struct VcfError {
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// 1-based column number (in characters) in the input.
+    pub column: usize,
+    pub kind: VcfErrorKind,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum VcfErrorKind {
+    #[error("expected BEGIN:VCARD")]
+    MissingBegin,
+    #[error("expected END:VCARD before the end of the input")]
+    MissingEnd,
+    #[error("unexpected content after END:VCARD")]
+    TrailingContent,
+    #[error("missing VERSION property")]
+    MissingVersion,
+    #[error("unsupported vCard version {0:?}")]
+    UnsupportedVersion(String),
+    #[error("expected a property name")]
+    ExpectedName,
+    #[error("expected a parameter name")]
+    ExpectedParameterName,
+    #[error("expected '=' after the parameter name")]
+    ExpectedEquals,
+    #[error("unterminated quoted parameter value")]
+    UnterminatedQuote,
+    #[error("expected ':' before the property value")]
+    ExpectedColon,
+    #[error("invalid {value_type} value {value:?}")]
+    InvalidValue { value_type: String, value: String },
+    #[error("unsupported character set {0:?}")]
+    UnsupportedCharset(String),
+}
+
+impl VcfError {
+    /// The [`VcfErrorKind::code`] of the error.
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+}
+
+impl VcfErrorKind {
+    /// A stable, kebab-case identifier of the variant, returned by [`Error::code`](crate::Error::code).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingBegin => "vcf-missing-begin",
+            Self::MissingEnd => "vcf-missing-end",
+            Self::TrailingContent => "vcf-trailing-content",
+            Self::MissingVersion => "vcf-missing-version",
+            Self::UnsupportedVersion(_) => "vcf-unsupported-version",
+            Self::ExpectedName => "vcf-expected-name",
+            Self::ExpectedParameterName => "vcf-expected-parameter-name",
+            Self::ExpectedEquals => "vcf-expected-equals",
+            Self::UnterminatedQuote => "vcf-unterminated-quote",
+            Self::ExpectedColon => "vcf-expected-colon",
+            Self::InvalidValue { .. } => "vcf-invalid-value",
+            Self::UnsupportedCharset(_) => "vcf-unsupported-charset",
+        }
+    }
+}
+
+/// The syntax version of the card being parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    V21,
+    V30,
+    V40,
+}
+
+impl Dialect {
+    fn is_legacy(self) -> bool {
+        self != Dialect::V40
+    }
+}
+
+impl Vcard {
+    /// Parses a single vCard in the text format.
+    ///
+    /// vCard 4.0, 3.0 and 2.1 are accepted, see the [module documentation](crate::vcf#legacy-versions) for how the
+    /// legacy versions are read. Both CRLF and bare LF line breaks are accepted, and folded lines are unfolded before
+    /// parsing.
+    pub fn from_vcf(input: &str) -> Result<Self, crate::Error> {
+        Ok(Self::parse_vcf(input)?)
+    }
+
+    fn parse_vcf(input: &str) -> Result<Self, VcfError> {
+        let lines = unfold(input);
+        let mut lines = lines.iter();
+
+        let end_of_input = || {
+            let line = input.lines().count().max(1);
+            let column = input.lines().last().map_or(0, |l| l.chars().count()) + 1;
+            (line, column)
+        };
+
+        let Some(begin) = lines.next() else {
+            let (line, column) = end_of_input();
+            return Err(VcfError {
+                line,
+                column,
+                kind: VcfErrorKind::MissingBegin,
+            });
+        };
+        let begin_line = begin.parse(Dialect::V21)?;
+        if !begin_line.is("BEGIN") || !begin_line.value.eq_ignore_ascii_case("VCARD") {
+            return Err(begin.error(0, VcfErrorKind::MissingBegin));
+        }
+
+        let Some((version_line, version)) = find_version(lines.as_slice())? else {
+            let (line, column) = end_of_input();
+            return Err(VcfError {
+                line,
+                column,
+                kind: VcfErrorKind::MissingVersion,
+            });
+        };
+        let dialect = match version.value.trim() {
+            "4.0" => Dialect::V40,
+            "3.0" => Dialect::V30,
+            "2.1" => Dialect::V21,
+            other => {
+                return Err(version_line.error(
+                    version.value_offset,
+                    VcfErrorKind::UnsupportedVersion(other.to_string()),
+                ))
+            }
+        };
+        let version = version.value.trim().to_string();
+
+        let mut version_seen = false;
+        let mut properties = Vec::new();
+
+        loop {
+            let Some(line) = lines.next() else {
+                let (line, column) = end_of_input();
+                return Err(VcfError {
+                    line,
+                    column,
+                    kind: VcfErrorKind::MissingEnd,
+                });
+            };
+            let content = line.parse(dialect)?;
+
+            if content.is("END") {
+                break;
+            }
+
+            if content.is("VERSION") && !version_seen {
+                version_seen = true;
+                continue;
+            }
+
+            let embeds_vcard = dialect.is_legacy()
+                && content.is("AGENT")
+                && content.value.trim().is_empty()
+                && lines.clone().next().is_some_and(|next| {
+                    next.text
+                        .split_once(':')
+                        .is_some_and(|(name, _)| name.eq_ignore_ascii_case("BEGIN"))
+                });
+
+            let mut property = content
+                .into_property(dialect)
+                .map_err(|(offset, kind)| line.error(offset, kind))?;
+
+            if embeds_vcard {
+                let mut depth = 0usize;
+                let mut embedded = Vec::new();
+                for line in lines.by_ref() {
+                    let content = line.parse(dialect)?;
+                    if content.is("BEGIN") {
+                        depth += 1;
+                    } else if content.is("END") {
+                        depth -= 1;
+                    }
+
+                    embedded.push(line.text.as_str());
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                property.values = vec![PropertyValue::String(embedded.join("\r\n"))];
+            }
+
+            properties.push(property);
+        }
+
+        if let Some(trailing) = lines.next() {
+            return Err(trailing.error(0, VcfErrorKind::TrailingContent));
+        }
+
+        Ok(Vcard {
+            version,
+            properties,
+        })
+    }
+
+    /// Writes the vCard in the text format, folding lines longer than 75 octets.
+    ///
+    /// The vCard 4.0 syntax is used regardless of [`Vcard::version`]. Multiple values are separated by commas, and
+    /// commas within values are escaped. Groups that are not made of letters, digits and dashes are left out.
+    ///
+    /// ```rust
+    /// # use vicardi::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut vcard = Vcard::default();
+    /// vcard.push(Property::new_fn("John Doe", None));
+    /// vcard.push(Property::new_multivalued("note", None, ValueType::Text, vec!["a,b".into(), "c".into()]));
+    /// vcard.push(Property::new("x-note", parameters! {"group" => "x:evil"}, ValueType::Text, "d"));
+    /// vcard.push(Property::new("note", None, ValueType::Text, "é".repeat(40)));
+    ///
+    /// let vcf = vcard.to_vcf();
+    /// assert!(vcf.contains("\r\nNOTE:a\\,b,c\r\nX-NOTE;VALUE=text:d\r\n"));
+    /// assert!(vcf.split("\r\n").all(|line| line.len() <= 75));
+    /// assert!(vcf.contains(&format!("\r\nNOTE:{}\r\n {}\r\n", "é".repeat(35), "é".repeat(5))));
+    ///
+    /// let parsed = Vcard::from_vcf(&vcf)?;
+    /// assert_eq!(parsed.properties[1], vcard.properties[1]);
+    /// assert_eq!(parsed.properties[2].group(), None);
+    /// assert_eq!(parsed.properties[3], vcard.properties[3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_vcf(&self) -> String {
+        let mut out = String::new();
+
+        fold("BEGIN:VCARD", &mut out);
+        fold(&format!("VERSION:{}", self.version), &mut out);
+        for property in &self.properties {
+            fold(&content_line(property), &mut out);
+        }
+        fold("END:VCARD", &mut out);
+
+        out
+    }
+}
+
+/// An unfolded content line together with the positions of its physical segments.
+struct LogicalLine {
+    text: String,
+    segments: Vec<Segment>,
+}
+
+struct Segment {
+    /// Byte offset in [`LogicalLine::text`] where this segment starts.
+    offset: usize,
+    /// 1-based physical line number.
+    line: usize,
+    /// 1-based character column of the segment's first character in the physical line.
+    column: usize,
+}
+
+/// Finds the first `VERSION` property of the outermost vCard, skipping the `BEGIN` line.
+fn find_version(
+    lines: &[LogicalLine],
+) -> Result<Option<(&LogicalLine, ContentLine<'_>)>, VcfError> {
+    let mut depth = 0usize;
+
+    for line in lines {
+        let content = line.parse(Dialect::V21)?;
+
+        if content.is("BEGIN") {
+            depth += 1;
+        } else if content.is("END") {
+            match depth.checked_sub(1) {
+                Some(outer) => depth = outer,
+                None => break,
+            }
+        } else if depth == 0 && content.is("VERSION") {
+            return Ok(Some((line, content)));
+        }
+    }
+
+    Ok(None)
+}
+
+fn unfold(input: &str) -> Vec<LogicalLine> {
+    let mut lines: Vec<LogicalLine> = Vec::new();
+
+    for (i, physical) in input.split('\n').enumerate() {
+        let physical = physical.strip_suffix('\r').unwrap_or(physical);
+
+        // Quoted-printable values end a line with `=` when they continue on the next one
+        if let Some(last) = lines
+            .last_mut()
+            .filter(|last| last.text.ends_with('=') && last.is_quoted_printable())
+        {
+            last.text.pop();
+            last.segments.push(Segment {
+                offset: last.text.len(),
+                line: i + 1,
+                column: 1,
+            });
+            last.text.push_str(physical);
+            continue;
+        }
+
+        match (physical.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => {
+                last.segments.push(Segment {
+                    offset: last.text.len(),
+                    line: i + 1,
+                    column: 2,
+                });
+                last.text.push_str(continuation);
+            }
+            _ if physical.trim().is_empty() => {}
+            _ => lines.push(LogicalLine {
+                text: physical.to_string(),
+                segments: vec![Segment {
+                    offset: 0,
+                    line: i + 1,
+                    column: 1,
+                }],
+            }),
+        }
+    }
+
+    lines
+}
+
+impl LogicalLine {
+    fn error(&self, offset: usize, kind: VcfErrorKind) -> VcfError {
+        let segment = self
+            .segments
+            .iter()
+            .rev()
+            .find(|segment| segment.offset <= offset)
+            .unwrap_or(&self.segments[0]);
+
+        VcfError {
+            line: segment.line,
+            column: segment.column + self.text[segment.offset..offset].chars().count(),
+            kind,
+        }
+    }
+
+    fn parse(&self, dialect: Dialect) -> Result<ContentLine<'_>, VcfError> {
+        ContentLine::parse(&self.text, dialect).map_err(|(offset, kind)| self.error(offset, kind))
+    }
+
+    fn is_quoted_printable(&self) -> bool {
+        self.text
+            .split_once(':')
+            .is_some_and(|(name, _)| name.to_ascii_uppercase().contains("QUOTED-PRINTABLE"))
+    }
+}
+
+/// `[group "."] name *(";" param) ":" value`
+struct ContentLine<'a> {
+    group: Option<&'a str>,
+    name: &'a str,
+    parameters: Vec<(String, Vec<String>)>,
+    value: &'a str,
+    /// Byte offset of [`ContentLine::value`] in the logical line.
+    value_offset: usize,
+}
+
+type Located<T> = Result<T, (usize, VcfErrorKind)>;
+
+impl<'a> ContentLine<'a> {
+    fn parse(line: &'a str, dialect: Dialect) -> Located<Self> {
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-';
+        let take_name = |from: usize| {
+            let len = line[from..]
+                .find(|c| !is_name_char(c))
+                .unwrap_or(line.len() - from);
+            &line[from..from + len]
+        };
+
+        let mut name = take_name(0);
+        if name.is_empty() {
+            return Err((0, VcfErrorKind::ExpectedName));
+        }
+        let mut pos = name.len();
+
+        let mut group = None;
+        if line[pos..].starts_with('.') {
+            group = Some(name);
+            name = take_name(pos + 1);
+            if name.is_empty() {
+                return Err((pos + 1, VcfErrorKind::ExpectedName));
+            }
+            pos += 1 + name.len();
+        }
+
+        // vCard 2.1 predates RFC 6868
+        let decode = |value: &str| match dialect {
+            Dialect::V21 => value.to_string(),
+            Dialect::V30 | Dialect::V40 => caret_decode(value).into_owned(),
+        };
+
+        let mut parameters: Vec<(String, Vec<String>)> = Vec::new();
+        while line[pos..].starts_with(';') {
+            pos += 1;
+            let key = take_name(pos);
+            if key.is_empty() {
+                return Err((pos, VcfErrorKind::ExpectedParameterName));
+            }
+            pos += key.len();
+            if !line[pos..].starts_with('=') {
+                if !dialect.is_legacy() {
+                    return Err((pos, VcfErrorKind::ExpectedEquals));
+                }
+
+                let bare = bare_parameter_key(key).to_string();
+                match parameters.iter_mut().find(|(k, _)| *k == bare) {
+                    Some((_, existing)) => existing.push(key.to_string()),
+                    None => parameters.push((bare, vec![key.to_string()])),
+                }
+                continue;
+            }
+
+            let mut values = Vec::new();
+            loop {
+                pos += 1;
+                if line[pos..].starts_with('"') {
+                    let Some(len) = line[pos + 1..].find('"') else {
+                        return Err((pos, VcfErrorKind::UnterminatedQuote));
+                    };
+                    values.push(decode(&line[pos + 1..pos + 1 + len]));
+                    pos += len + 2;
+                } else {
+                    let len = line[pos..]
+                        .find([';', ':', ','])
+                        .unwrap_or(line.len() - pos);
+                    values.push(decode(&line[pos..pos + len]));
+                    pos += len;
+                }
+
+                if !line[pos..].starts_with(',') {
+                    break;
+                }
+            }
+
+            let key = key.to_lowercase();
+            match parameters.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, existing)) => existing.extend(values),
+                None => parameters.push((key, values)),
+            }
+        }
+
+        if !line[pos..].starts_with(':') {
+            return Err((pos, VcfErrorKind::ExpectedColon));
+        }
+
+        Ok(Self {
+            group,
+            name,
+            parameters,
+            value: &line[pos + 1..],
+            value_offset: pos + 1,
+        })
+    }
+
+    fn is(&self, name: &str) -> bool {
+        self.group.is_none() && self.name.eq_ignore_ascii_case(name)
+    }
+
+    fn into_property(self, dialect: Dialect) -> Located<Property> {
+        let name = self.name.to_lowercase();
+        let legacy = dialect.is_legacy();
+
+        let mut parameters = Parameters::with_capacity(self.parameters.len() + 1);
+        if let Some(group) = self.group {
+            parameters.insert("group".into(), vec![group.to_string()]);
+        }
+        let mut value_type = None;
+        let mut encoding = None;
+        let mut charset = None;
+        for (key, mut values) in self.parameters {
+            match key.as_str() {
+                "value" => value_type = values.into_iter().next().map(|v| v.to_lowercase()),
+                "encoding" if legacy => {
+                    encoding = values.into_iter().next().map(|v| v.to_lowercase())
+                }
+                "charset" if legacy => charset = values.into_iter().next(),
+                "type" if legacy => {
+                    values.iter_mut().for_each(|v| *v = v.to_lowercase());
+                    parameters.insert(key, values);
+                }
+                _ => {
+                    parameters.insert(key, values);
+                }
+            }
+        }
+
+        let raw = match encoding.as_deref() {
+            Some("quoted-printable") => Cow::Owned(
+                decode_quoted_printable(self.value, charset.as_deref())
+                    .map_err(|kind| (self.value_offset, kind))?,
+            ),
+            Some("b" | "base64") => {
+                parameters.insert("encoding".into(), vec!["b".into()]);
+                let data: String = self.value.split_whitespace().collect();
+                let value_type = value_type.unwrap_or_else(|| "binary".into());
+                return Ok(Property::new(name, parameters, value_type, data));
+            }
+            _ => Cow::Borrowed(self.value),
+        };
+
+        let mut value_type = match (value_type.as_deref(), legacy) {
+            (Some("url" | "content-id" | "cid"), true) => "uri".to_string(),
+            (Some("inline") | None, true) => legacy_default_value_type(&name).to_string(),
+            (Some(value_type), _) => value_type.to_string(),
+            (None, false) => PropertyName::from(name.as_str())
+                .default_value_type()
+                .to_string(),
+        };
+
+        let values = match decode_values(&name, &value_type, &raw, dialect) {
+            Ok(values) => values,
+            // Legacy cards often use a date where a timestamp is expected, or free text where a typed value is
+            Err(_) if legacy => match decode_values(&name, "date-and-or-time", &raw, dialect) {
+                Ok(values) if is_datetime(&value_type) => {
+                    value_type = "date-and-or-time".into();
+                    values
+                }
+                _ => {
+                    value_type = "text".into();
+                    vec![PropertyValue::String(unescape(&raw))]
+                }
+            },
+            Err(kind) => return Err((self.value_offset, kind)),
+        };
+
+        Ok(Property::new_multivalued(
+            name, parameters, value_type, values,
+        ))
+    }
+}
+
+/// The value type of a vCard 3.0 or 2.1 property when no `VALUE` parameter is given.
+fn legacy_default_value_type(name: &str) -> ValueType {
+    match name {
+        "tz" => ValueType::UtcOffset,
+        "geo" | "uid" | "key" | "label" | "mailer" | "sort-string" | "class" | "name"
+        | "profile" | "agent" => ValueType::Text,
+        _ => PropertyName::from(name).default_value_type(),
+    }
+}
+
+/// Maps a parameter without a name (`TEL;WORK;VOICE:`) to the parameter it is a value of.
+fn bare_parameter_key(value: &str) -> &'static str {
+    match value.to_ascii_uppercase().as_str() {
+        "7BIT" | "8BIT" | "QUOTED-PRINTABLE" | "BASE64" | "B" => "encoding",
+        "INLINE" | "URL" | "URI" | "CONTENT-ID" | "CID" => "value",
+        _ => "type",
+    }
+}
+
+/// Whether the property has a compound (`;` separated) value.
+fn is_structured(name: &str) -> bool {
+    matches!(name, "n" | "adr" | "org" | "gender" | "clientpidmap")
+}
+
+/// Whether the components of a structured property can hold multiple `,` separated values.
+///
+/// vCard 2.1 does not escape commas, so its components are always single values.
+fn has_list_components(name: &str, dialect: Dialect) -> bool {
+    dialect != Dialect::V21 && matches!(name, "n" | "adr")
+}
+
+/// Whether a text value is a `,` separated list of values.
+///
+/// vCard 4.0 escapes commas in text values, so an unescaped comma always separates values. Legacy cards often leave
+/// them unescaped, so only the properties defined as lists are split.
+fn is_list(name: &str, dialect: Dialect) -> bool {
+    matches!(name, "nickname" | "categories") || !dialect.is_legacy()
+}
+
+fn decode_values(
+    name: &str,
+    value_type: &str,
+    raw: &str,
+    dialect: Dialect,
+) -> Result<Vec<PropertyValue>, VcfErrorKind> {
+    let invalid = || VcfErrorKind::InvalidValue {
+        value_type: value_type.to_string(),
+        value: raw.to_string(),
+    };
+
+    Ok(match value_type {
+        "text" if is_structured(name) => {
+            let mut components: Vec<PropertyValue> = split_unescaped(raw, ';')
+                .map(|component| {
+                    if !has_list_components(name, dialect) {
+                        return PropertyValue::String(unescape(component));
+                    }
+
+                    let mut values: Vec<PropertyValue> = split_unescaped(component, ',')
+                        .map(|v| PropertyValue::String(unescape(v)))
+                        .collect();
+                    match values.len() {
+                        1 => values.remove(0),
+                        _ => PropertyValue::Structured(values),
+                    }
+                })
+                .collect();
+
+            match components.len() {
+                1 => vec![components.remove(0)],
+                _ => vec![PropertyValue::Structured(components)],
+            }
+        }
+        "text" if is_list(name, dialect) => split_unescaped(raw, ',')
+            .map(|v| PropertyValue::String(unescape(v)))
+            .collect(),
+        "text" => vec![PropertyValue::String(unescape(raw))],
+        "integer" => raw
+            .split(',')
+            .map(|v| v.trim().parse().map(PropertyValue::Integer))
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid())?,
+        "float" => raw
+            .split(',')
+            .map(|v| v.trim().parse().map(PropertyValue::Float))
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid())?,
+        "boolean" => match raw.to_ascii_uppercase().as_str() {
+            "TRUE" => vec![PropertyValue::Bool(true)],
+            "FALSE" => vec![PropertyValue::Bool(false)],
+            _ => return Err(invalid()),
+        },
+        value_type if is_datetime(value_type) => raw
+            .split(',')
+            .map(|v| datetime_format(value_type, v, true).map(PropertyValue::String))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)?,
+        _ => vec![PropertyValue::String(raw.to_string())],
+    })
+}
+
+fn decode_quoted_printable(value: &str, charset: Option<&str>) -> Result<String, VcfErrorKind> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| bytes[i] == b'=' && hex.iter().all(u8::is_ascii_hexdigit));
+
+        match hex {
+            Some(hex) => {
+                let hex = std::str::from_utf8(hex).expect("hex digits are ASCII");
+                decoded.push(u8::from_str_radix(hex, 16).expect("valid hex digits"));
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    match charset.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("utf-8" | "utf8" | "us-ascii" | "ascii") => {
+            String::from_utf8(decoded).map_err(|_| VcfErrorKind::InvalidValue {
+                value_type: "quoted-printable".into(),
+                value: value.to_string(),
+            })
+        }
+        Some("iso-8859-1" | "latin1" | "latin-1") => {
+            Ok(decoded.into_iter().map(char::from).collect())
+        }
+        Some("windows-1252" | "cp1252") => Ok(decoded.into_iter().map(windows_1252).collect()),
+        Some(other) => Err(VcfErrorKind::UnsupportedCharset(other.to_string())),
+    }
+}
+
+/// Windows-1252 is ISO-8859-1 with printable characters in the `0x80..=0x9F` range.
+fn windows_1252(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž',
+        '\u{8F}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}',
+        'ž', 'Ÿ',
+    ];
+
+    match byte {
+        0x80..=0x9F => HIGH[usize::from(byte - 0x80)],
+        byte => char::from(byte),
+    }
+}
+
+/// Splits on `separator`s that are not escaped with a backslash.
+fn split_unescaped(value: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut escaped = false;
+    value.split(move |c| {
+        let split = c == separator && !escaped;
+        escaped = c == '\\' && !escaped;
+        split
+    })
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            },
+            (c, false) => out.push(c),
+        }
+    }
+
+    out
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ',' => out.push_str("\\,"),
+            ';' => out.push_str("\\;"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+fn content_line(property: &Property) -> String {
+    let mut line = String::new();
+
+    // An invalid group would change where the property name starts, so it is left out
+    if let Some(group) = property.group().filter(|group| is_group(group)) {
+        line.push_str(group);
+        line.push('.');
+    }
+    line.push_str(&property.name.to_uppercase());
+
+    if property.value_type != property.property_name().default_value_type() {
+        line.push_str(";VALUE=");
+        line.push_str(property.value_type.as_str());
+    }
+
+    for (key, values) in &property.parameters {
+        if key == "group" {
+            continue;
+        }
+
+        line.push(';');
+        line.push_str(&key.to_uppercase());
+        line.push('=');
+
+        let values: Vec<String> = values
+            .iter()
+            .map(|value| {
+                let value = caret_encode(value);
+                match value.contains([';', ':', ',']) {
+                    true => format!("\"{value}\""),
+                    false => value.into_owned(),
+                }
+            })
+            .collect();
+        line.push_str(&values.join(","));
+    }
+
+    line.push(':');
+
+    let values: Vec<String> = property
+        .values
+        .iter()
+        .map(|value| encode_value(property.value_type.as_str(), value))
+        .collect();
+    line.push_str(&values.join(","));
+
+    line
+}
+
+fn encode_value(value_type: &str, value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::String(string) => match value_type {
+            "text" => escape(string),
+            _ => datetime_format(value_type, string, false).unwrap_or_else(|| string.clone()),
+        },
+        PropertyValue::Bool(true) => "TRUE".into(),
+        PropertyValue::Bool(false) => "FALSE".into(),
+        PropertyValue::Integer(int) => int.to_string(),
+        PropertyValue::Float(float) => float.to_string(),
+        PropertyValue::Structured(components) => components
+            .iter()
+            .map(|component| match component {
+                PropertyValue::Structured(values) => values
+                    .iter()
+                    .map(|value| encode_value(value_type, value))
+                    .collect::<Vec<_>>()
+                    .join(","),
+                single => encode_value(value_type, single),
+            })
+            .collect::<Vec<_>>()
+            .join(";"),
+    }
+}
+
+/// Appends a content line to `out`, folding it into chunks of at most [`MAX_LINE_OCTETS`] octets without splitting
+/// UTF-8 sequences.
+fn fold(line: &str, out: &mut String) {
+    let mut start = 0;
+    let mut limit = MAX_LINE_OCTETS;
+
+    for (i, c) in line.char_indices() {
+        if i + c.len_utf8() - start > limit {
+            out.push_str(&line[start..i]);
+            out.push_str("\r\n ");
+            start = i;
+            // The leading space of a continuation line counts towards the limit
+            limit = MAX_LINE_OCTETS - 1;
+        }
+    }
+
+    out.push_str(&line[start..]);
+    out.push_str("\r\n");
+}
@@ -0,0 +1,635 @@
+//! Semantic conversions between vCard versions.
+//!
+//! [`Vcard::from_vcf`] only converts the syntax of vCard 3.0 and 2.1 cards. The conversions in this module rewrite the
+//! properties and parameters that changed meaning between the versions, as described in
+//! [RFC 6350, Appendix A](https://datatracker.ietf.org/doc/html/rfc6350#appendix-A).
+use crate::{tel_number, Parameters, Property, PropertyValue, ValueType, Vcard};
+
+/// The changes made by a version conversion that lost information.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConversionReport {
+    pub changes: Vec<LossyChange>,
+}
+
+/// A single lossy change, e.g. a dropped property or parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LossyChange {
+    /// Name of the affected property.
+    pub property: String,
+    /// Human readable description of what was lost.
+    pub reason: String,
+}
+
+impl ConversionReport {
+    /// Whether the conversion kept all of the information in the vCard.
+    pub fn is_lossless(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn lose(&mut self, property: impl ToString, reason: impl ToString) {
+        self.changes.push(LossyChange {
+            property: property.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// Properties that only exist in vCard 3.0 or 2.1 and have no vCard 4.0 equivalent.
+const LEGACY_ONLY: &[&str] = &["name", "mailer", "class", "profile"];
+
+/// Properties that only exist in vCard 4.0 (or its extensions) and have no vCard 3.0 equivalent.
+const V4_ONLY: &[&str] = &[
+    "lang",
+    "clientpidmap",
+    "xml",
+    "birthplace",
+    "deathplace",
+    "deathdate",
+    "expertise",
+    "hobby",
+    "interest",
+    "org-directory",
+];
+
+/// Parameters that only exist in vCard 4.0 (or its extensions) and have no vCard 3.0 equivalent.
+const V4_ONLY_PARAMETERS: &[&str] = &["pid", "calscale", "geo", "tz", "index", "level", "cc"];
+
+/// vCard 3.0 address types that were removed in vCard 4.0.
+const DELIVERY_TYPES: [&str; 4] = ["dom", "intl", "postal", "parcel"];
+
+/// Extension properties that were commonly used for the gender before vCard 4.0.
+const GENDER_EXTENSIONS: &[&str] = &["x-gender", "x-sex", "x-wab-gender"];
+
+impl Vcard {
+    /// Rewrites the properties of a vCard 3.0 or 2.1 card into their vCard 4.0 form and sets [`Vcard::version`] to
+    /// `"4.0"`.
+    ///
+    /// - `TYPE=pref` becomes `PREF=1`,
+    /// - `LABEL` properties become the `label` parameter of the `ADR` with the same types,
+    /// - `AGENT` becomes `RELATED;TYPE=agent`,
+    /// - inline `ENCODING=b` media in `PHOTO`, `LOGO`, `SOUND` and `KEY` becomes a `data:` URI,
+    /// - `X-GENDER`, `X-SEX` and `X-WAB-GENDER` become `GENDER`,
+    /// - `SORT-STRING` becomes the `sort-as` parameter of `N`,
+    /// - a `GEO` latitude and longitude pair becomes a `geo:` URI.
+    ///
+    /// Cards that already are vCard 4.0 are left untouched.
+    ///
+    /// ```rust
+    /// # use vicardi::*;
+    /// # use serde_json::json;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut vcard = Vcard::from_vcf(
+    ///     "BEGIN:VCARD\r\n\
+    ///      VERSION:3.0\r\n\
+    ///      FN:John Doe\r\n\
+    ///      TEL;TYPE=work,pref:+1-555-555-5555\r\n\
+    ///      PHOTO;ENCODING=b;TYPE=JPEG:/9j/4AAQSkZJRgABAQ==\r\n\
+    ///      MAILER:PigeonMail 2.1\r\n\
+    ///      END:VCARD\r\n",
+    /// )?;
+    ///
+    /// let report = vcard.upgrade_to_v4();
+    ///
+    /// let json = json!([
+    ///     "vcard",
+    ///     [
+    ///         ["version", {}, "text", "4.0"],
+    ///         ["fn", {}, "text", "John Doe"],
+    ///         ["tel", {"type": "work", "pref": "1"}, "text", "+1-555-555-5555"],
+    ///         ["photo", {}, "uri", "data:image/jpeg;base64,/9j/4AAQSkZJRgABAQ=="]
+    ///     ]
+    /// ]);
+    ///
+    /// assert_eq!(serde_json::to_value(&vcard)?, json);
+    /// assert_eq!(report.changes.len(), 1);
+    /// assert_eq!(report.changes[0].property, "mailer");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn upgrade_to_v4(&mut self) -> ConversionReport {
+        let mut report = ConversionReport::default();
+        if self.version == "4.0" {
+            return report;
+        }
+
+        let mut labels = Vec::new();
+        let mut sort_string = None;
+        let mut genders = Vec::new();
+
+        for mut property in std::mem::take(&mut self.properties) {
+            let name = property.name.to_lowercase();
+
+            if remove_type(&mut property.parameters, "pref") {
+                property
+                    .parameters
+                    .entry("pref".into())
+                    .or_insert_with(|| vec!["1".into()]);
+            }
+
+            match name.as_str() {
+                "label" => {
+                    for delivery in DELIVERY_TYPES {
+                        remove_type(&mut property.parameters, delivery);
+                    }
+                    labels.push(property);
+                    continue;
+                }
+                "sort-string" => {
+                    sort_string = text_value(&property);
+                    continue;
+                }
+                name if GENDER_EXTENSIONS.contains(&name) => {
+                    genders.push(property);
+                    continue;
+                }
+                name if LEGACY_ONLY.contains(&name) => {
+                    report.lose(&property.name, "the property does not exist in vCard 4.0");
+                    continue;
+                }
+                "agent" => {
+                    property.name = "related".into();
+                    add_type(&mut property.parameters, "agent");
+                }
+                "photo" | "logo" | "sound" | "key" => inline_to_data_uri(&mut property, &name),
+                "geo" => geo_to_uri(&mut property),
+                "email" => {
+                    // Every vCard 4.0 email address is an internet address
+                    remove_type(&mut property.parameters, "internet");
+                }
+                "adr" => {
+                    for delivery in DELIVERY_TYPES {
+                        if remove_type(&mut property.parameters, delivery) {
+                            report.lose(
+                                &property.name,
+                                format!(
+                                    "the {delivery:?} address type does not exist in vCard 4.0"
+                                ),
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            self.properties.push(property);
+        }
+
+        for label in labels {
+            let Some(text) = text_value(&label) else {
+                report.lose(&label.name, "the label has no text value");
+                continue;
+            };
+
+            let label_types = sorted_types(&label.parameters);
+            let adr = self.properties.iter_mut().find(|property| {
+                property.name.eq_ignore_ascii_case("adr")
+                    && !property.parameters.contains_key("label")
+                    && sorted_types(&property.parameters) == label_types
+            });
+
+            match adr {
+                Some(adr) => {
+                    adr.parameters.insert("label".into(), vec![text]);
+                }
+                None => {
+                    // An address with only a label is how RFC 6350 represents an unstructured address
+                    let mut parameters = label.parameters;
+                    parameters.insert("label".into(), vec![text]);
+                    self.properties.push(Property::new(
+                        "adr",
+                        parameters,
+                        ValueType::Text,
+                        PropertyValue::Structured(vec!["".into(); 7]),
+                    ));
+                }
+            }
+        }
+
+        if let Some(sort_string) = sort_string {
+            match self
+                .properties
+                .iter_mut()
+                .find(|property| property.name.eq_ignore_ascii_case("n"))
+            {
+                Some(n) => {
+                    n.parameters.insert("sort-as".into(), vec![sort_string]);
+                }
+                None => report.lose("sort-string", "there is no N property to attach sort-as to"),
+            }
+        }
+
+        let has_gender = self
+            .properties
+            .iter()
+            .any(|property| property.name.eq_ignore_ascii_case("gender"));
+        for (i, gender) in genders.into_iter().enumerate() {
+            match (has_gender || i > 0, gender_from_extension(&gender)) {
+                (false, Some(value)) => {
+                    self.properties
+                        .push(Property::new("gender", None, ValueType::Text, value))
+                }
+                (true, _) => report.lose(&gender.name, "the vCard already has a gender"),
+                (false, None) => report.lose(&gender.name, "the gender has no text value"),
+            }
+        }
+
+        self.version = "4.0".into();
+        report
+    }
+
+    /// Rewrites the properties of a vCard 4.0 card into their vCard 3.0 form and sets [`Vcard::version`] to `"3.0"`.
+    ///
+    /// This is the reverse of [`Vcard::upgrade_to_v4`]. Additionally:
+    ///
+    /// - only the first property of every `ALTID` group is kept,
+    /// - `PREF` values other than 1 and parameters that do not exist in vCard 3.0 are dropped,
+    /// - `TEL` URIs become text values, with an `;ext=` extension written as ` x` and the extension, and other URI
+    ///   parameters dropped; both are reported,
+    /// - `KIND`, `ANNIVERSARY` and `MEMBER` become their widely supported `X-` equivalents,
+    /// - other properties that do not exist in vCard 3.0 are dropped.
+    ///
+    /// Cards that are not vCard 4.0 are left untouched.
+    ///
+    /// ```rust
+    /// # use vicardi::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut vcard = Vcard::default();
+    /// vcard.push(Property::new_fn("John Doe", None));
+    /// vcard.push(Property::new_tel(Telephone::Voice, "+1-555-555-5555", parameters! {"pref" => "1"}));
+    /// vcard.push(Property::new("gender", None, "text", "M"));
+    /// vcard.push(Property::new("lang", None, "language-tag", "en"));
+    ///
+    /// let report = vcard.downgrade_to_v3();
+    ///
+    /// assert_eq!(
+    ///     vcard.to_vcf(),
+    ///     "BEGIN:VCARD\r\n\
+    ///      VERSION:3.0\r\n\
+    ///      FN:John Doe\r\n\
+    ///      TEL;TYPE=voice,pref:+1-555-555-5555\r\n\
+    ///      X-GENDER;VALUE=text:Male\r\n\
+    ///      END:VCARD\r\n"
+    /// );
+    /// assert_eq!(report.changes.len(), 1);
+    /// assert_eq!(report.changes[0].property, "lang");
+    ///
+    /// let mut vcard = Vcard::default();
+    /// vcard.push(Property::new_tel(Telephone::Voice, "+1;ext=2", None));
+    /// let report = vcard.downgrade_to_v3();
+    /// assert!(vcard.to_vcf().contains("\r\nTEL;TYPE=voice:+1 x2\r\n"));
+    /// assert_eq!(report.changes[0].property, "tel");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn downgrade_to_v3(&mut self) -> ConversionReport {
+        let mut report = ConversionReport::default();
+        if self.version != "4.0" {
+            return report;
+        }
+
+        let mut altids = Vec::new();
+        let mut appended = Vec::new();
+
+        for mut property in std::mem::take(&mut self.properties) {
+            let name = property.name.to_lowercase();
+
+            if let Some(altid) = property.parameters.shift_remove("altid") {
+                let key = (name.clone(), altid);
+                if altids.contains(&key) {
+                    report.lose(
+                        &property.name,
+                        "alternative representations are not supported in vCard 3.0",
+                    );
+                    continue;
+                }
+                altids.push(key);
+            }
+
+            for parameter in V4_ONLY_PARAMETERS {
+                if property.parameters.shift_remove(*parameter).is_some() {
+                    report.lose(
+                        &property.name,
+                        format!("the {parameter:?} parameter does not exist in vCard 3.0"),
+                    );
+                }
+            }
+
+            match property.parameters.shift_remove("pref").as_deref() {
+                Some([pref]) if pref == "1" => add_type(&mut property.parameters, "pref"),
+                Some(_) => report.lose(
+                    &property.name,
+                    "only the most preferred value can be marked in vCard 3.0",
+                ),
+                None => {}
+            }
+
+            if let Some(sort_as) = property.parameters.shift_remove("sort-as") {
+                match name.as_str() {
+                    "n" => appended.push(Property::new(
+                        "sort-string",
+                        None,
+                        ValueType::Text,
+                        sort_as.join(" "),
+                    )),
+                    _ => report.lose(
+                        &property.name,
+                        "sort-as is only supported on N in vCard 3.0",
+                    ),
+                }
+            }
+
+            match name.as_str() {
+                name if V4_ONLY.contains(&name) => {
+                    report.lose(&property.name, "the property does not exist in vCard 3.0");
+                    continue;
+                }
+                "related" => {
+                    if !remove_type(&mut property.parameters, "agent") {
+                        report.lose(&property.name, "the property does not exist in vCard 3.0");
+                        continue;
+                    }
+                    property.name = "agent".into();
+                    property.parameters.shift_remove("type");
+                }
+                "kind" => property.name = "x-addressbookserver-kind".into(),
+                "anniversary" => property.name = "x-anniversary".into(),
+                "member" => property.name = "x-addressbookserver-member".into(),
+                "adr" => {
+                    if let Some(label) = property.parameters.shift_remove("label") {
+                        let mut parameters = Parameters::new();
+                        if let Some(types) = property.parameters.get("type") {
+                            parameters.insert("type".into(), types.clone());
+                        }
+                        appended.push(Property::new(
+                            "label",
+                            parameters,
+                            ValueType::Text,
+                            label.join("\n"),
+                        ));
+                    }
+                }
+                "gender" => gender_to_extension(&mut property, &mut report),
+                "geo" => geo_from_uri(&mut property, &mut report),
+                "tel" if property.value_type == ValueType::Uri => {
+                    for value in &mut property.values {
+                        let PropertyValue::String(uri) = value else {
+                            continue;
+                        };
+                        if !uri.starts_with("tel:") {
+                            continue;
+                        }
+                        // vCard 3.0 numbers are text, an extension is commonly written as " x1234"
+                        let (number, ext) = tel_number(uri);
+                        let text = match ext {
+                            Some(ext) => format!("{number} x{ext}"),
+                            None => number.to_string(),
+                        };
+                        if uri.contains(';') {
+                            report.lose(
+                                &property.name,
+                                format!("the parameters of {uri} were dropped, the number is written as {text:?}"),
+                            );
+                        }
+                        *uri = text;
+                    }
+                    property.value_type = ValueType::Text;
+                }
+                "photo" | "logo" | "sound" | "key" => data_uri_to_inline(&mut property),
+                _ => {}
+            }
+
+            self.properties.push(property);
+        }
+
+        self.properties.extend(appended);
+        self.version = "3.0".into();
+        report
+    }
+}
+
+fn text_value(property: &Property) -> Option<String> {
+    match property.values.first() {
+        Some(PropertyValue::String(text)) => Some(text.clone()),
+        _ => None,
+    }
+}
+
+fn sorted_types(parameters: &Parameters) -> Vec<String> {
+    let mut types: Vec<String> = parameters
+        .get("type")
+        .into_iter()
+        .flatten()
+        .map(|t| t.to_lowercase())
+        .collect();
+    types.sort();
+    types
+}
+
+fn add_type(parameters: &mut Parameters, value: &str) {
+    let types = parameters.entry("type".into()).or_default();
+    if !types.iter().any(|t| t.eq_ignore_ascii_case(value)) {
+        types.push(value.into());
+    }
+}
+
+/// Removes a `type` parameter value, returning whether it was present.
+fn remove_type(parameters: &mut Parameters, value: &str) -> bool {
+    let Some(types) = parameters.get_mut("type") else {
+        return false;
+    };
+
+    let len = types.len();
+    types.retain(|t| !t.eq_ignore_ascii_case(value));
+    let removed = types.len() != len;
+
+    if types.is_empty() {
+        parameters.shift_remove("type");
+    }
+
+    removed
+}
+
+/// The media type for an inline vCard 3.0 `TYPE` such as `JPEG`.
+fn media_type(property: &str, format: &str) -> String {
+    let format = format.to_lowercase();
+    if format.contains('/') {
+        return format;
+    }
+
+    match (property, format.as_str()) {
+        ("key", "pgp") => "application/pgp-keys".into(),
+        ("key", "x509") => "application/pkix-cert".into(),
+        ("key", format) => format!("application/{format}"),
+        ("sound", "wave" | "wav") => "audio/wav".into(),
+        ("sound", "mp3") => "audio/mpeg".into(),
+        ("sound", format) => format!("audio/{format}"),
+        (_, "jpg") => "image/jpeg".into(),
+        (_, format) => format!("image/{format}"),
+    }
+}
+
+/// The vCard 3.0 `TYPE` for a media type such as `image/jpeg`.
+fn media_format(media_type: &str) -> String {
+    match media_type {
+        "application/pgp-keys" => "PGP".into(),
+        "application/pkix-cert" => "X509".into(),
+        "audio/wav" => "WAVE".into(),
+        "audio/mpeg" => "MP3".into(),
+        other => other.rsplit('/').next().unwrap_or(other).to_uppercase(),
+    }
+}
+
+fn inline_to_data_uri(property: &mut Property, name: &str) {
+    if property.parameters.shift_remove("encoding").is_none() {
+        return;
+    }
+
+    let media_type = match property.parameters.shift_remove("type").as_deref() {
+        Some([format, ..]) => media_type(name, format),
+        _ => "application/octet-stream".into(),
+    };
+
+    for value in &mut property.values {
+        if let PropertyValue::String(data) = value {
+            *data = format!("data:{media_type};base64,{data}");
+        }
+    }
+    property.value_type = ValueType::Uri;
+}
+
+fn data_uri_to_inline(property: &mut Property) {
+    let Some(PropertyValue::String(uri)) = property.values.first() else {
+        return;
+    };
+    let Some((media_type, data)) = uri
+        .strip_prefix("data:")
+        .and_then(|uri| uri.split_once(";base64,"))
+    else {
+        return;
+    };
+
+    let format = media_format(media_type);
+    property.values = vec![PropertyValue::String(data.to_string())];
+    property.value_type = "binary".into();
+    property.parameters.shift_remove("mediatype");
+    property
+        .parameters
+        .insert("encoding".into(), vec!["b".into()]);
+    property.parameters.insert("type".into(), vec![format]);
+}
+
+fn geo_to_uri(property: &mut Property) {
+    if property.value_type != ValueType::Text {
+        return;
+    }
+
+    let (latitude, longitude) = match property.values.first() {
+        Some(PropertyValue::String(text)) => match text.split_once([';', ',']) {
+            Some(coordinates) => coordinates,
+            None => return,
+        },
+        Some(PropertyValue::Structured(components)) => match components.as_slice() {
+            [PropertyValue::String(latitude), PropertyValue::String(longitude)] => {
+                (latitude.as_str(), longitude.as_str())
+            }
+            _ => return,
+        },
+        _ => return,
+    };
+
+    property.values = vec![PropertyValue::String(format!(
+        "geo:{},{}",
+        latitude.trim(),
+        longitude.trim()
+    ))];
+    property.value_type = ValueType::Uri;
+}
+
+fn geo_from_uri(property: &mut Property, report: &mut ConversionReport) {
+    let Some(PropertyValue::String(uri)) = property.values.first() else {
+        return;
+    };
+    let Some(coordinates) = uri.strip_prefix("geo:") else {
+        report.lose(
+            &property.name,
+            "only geo: URIs can be represented in vCard 3.0",
+        );
+        return;
+    };
+
+    let (coordinates, uri_parameters) = coordinates
+        .split_once(';')
+        .map_or((coordinates, None), |(c, p)| (c, Some(p)));
+    let mut coordinates = coordinates.split(',');
+    let (Some(latitude), Some(longitude)) = (coordinates.next(), coordinates.next()) else {
+        return;
+    };
+
+    if coordinates.next().is_some() || uri_parameters.is_some() {
+        report.lose(
+            &property.name,
+            "the altitude and URI parameters are not supported in vCard 3.0",
+        );
+    }
+
+    property.values = vec![PropertyValue::Structured(vec![
+        latitude.into(),
+        longitude.into(),
+    ])];
+    property.value_type = ValueType::Text;
+}
+
+fn gender_from_extension(property: &Property) -> Option<PropertyValue> {
+    let text = text_value(property)?;
+    let wab = property.name.eq_ignore_ascii_case("x-wab-gender");
+
+    let sex = match text.trim().to_lowercase().as_str() {
+        "1" if wab => "F",
+        "2" if wab => "M",
+        "m" | "male" => "M",
+        "f" | "female" => "F",
+        "o" | "other" => "O",
+        "n" | "none" => "N",
+        "u" | "unknown" => "U",
+        "" => return None,
+        // Free text is kept as the gender identity component
+        _ => return Some(PropertyValue::Structured(vec!["".into(), text.into()])),
+    };
+
+    Some(sex.into())
+}
+
+fn gender_to_extension(property: &mut Property, report: &mut ConversionReport) {
+    let (sex, identity) = match property.values.first() {
+        Some(PropertyValue::String(sex)) => (sex.clone(), None),
+        Some(PropertyValue::Structured(components)) => match components.as_slice() {
+            [PropertyValue::String(sex), PropertyValue::String(identity), ..] => (
+                sex.clone(),
+                Some(identity.clone()).filter(|i| !i.is_empty()),
+            ),
+            [PropertyValue::String(sex)] => (sex.clone(), None),
+            _ => (String::new(), None),
+        },
+        _ => (String::new(), None),
+    };
+
+    let text = match (sex.to_uppercase().as_str(), identity) {
+        ("M", identity) => ("Male".to_string(), identity),
+        ("F", identity) => ("Female".to_string(), identity),
+        ("O", identity) => ("Other".to_string(), identity),
+        ("N", identity) => ("None".to_string(), identity),
+        ("U", identity) => ("Unknown".to_string(), identity),
+        (_, Some(identity)) => (identity, None),
+        (_, None) => (sex, None),
+    };
+
+    if text.1.is_some() {
+        report.lose(
+            &property.name,
+            "the gender identity is not supported in vCard 3.0",
+        );
+    }
+
+    property.name = "x-gender".into();
+    property.values = vec![PropertyValue::String(text.0)];
+}
@@ -0,0 +1,196 @@
+//! WHOIS (port 43) contact blocks
+//!
+//! [`Vcard::to_whois`] renders a vCard as the contact block of a WHOIS response, in the key-value format of the
+//! ICANN Registration Data Directory Services specifications, e.g. `Registrant Name: Ivan Petrov`. Every field is
+//! written, fields without a value have an empty value:
+//!
+//! - `Name` and `Organization` are the first `fn` and `org`,
+//! - `Street` (once per street line), `City`, `State/Province`, `Postal Code` and `Country` come from the first
+//!   `adr`, the country being its `cc` parameter if it has one,
+//! - `Phone`, `Phone Ext`, `Fax` and `Fax Ext` come from the first `tel` without and with the
+//!   [`Telephone::Fax`] type, the extension being the `;ext=` parameter of a `tel:` URI,
+//! - `Email` is the first `email`.
+//!
+//! Redaction is described by the names of the redacted fields, such as the `name` types of the RFC 9537
+//! `redacted` members produced by redacting the same vCard for RDAP (`Registrant Phone`). Redacted fields are
+//! written as [`REDACTED`], and the extension of a redacted phone or fax number is redacted with it.
+//!
+//! Control characters such as line breaks in a value are replaced with spaces, so a value cannot start a new field.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # fn main() -> anyhow::Result<()> {
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new_fn("Ivan Petrov", None));
+//! vcard.push(Property::new_adr(
+//!     ["".into(), "".into(), "Amir Temur 4".into(), "Tashkent".into(), "".into(), "100000".into(), "".into()].into(),
+//!     parameters! {"cc" => "UZ"},
+//! ));
+//! vcard.push(Property::new_tel(Telephone::Voice, "+998.711234567;ext=1234", None));
+//! vcard.push(Property::new_email("https://example.uz/contact", None));
+//!
+//! assert_eq!(
+//!     vcard.to_whois("Registrant", &["Registrant Name", "Registrant Phone"]),
+//!     "Registrant Name: REDACTED FOR PRIVACY\r\n\
+//!      Registrant Organization:\r\n\
+//!      Registrant Street: Amir Temur 4\r\n\
+//!      Registrant City: Tashkent\r\n\
+//!      Registrant State/Province:\r\n\
+//!      Registrant Postal Code: 100000\r\n\
+//!      Registrant Country: UZ\r\n\
+//!      Registrant Phone: REDACTED FOR PRIVACY\r\n\
+//!      Registrant Phone Ext: REDACTED FOR PRIVACY\r\n\
+//!      Registrant Fax:\r\n\
+//!      Registrant Fax Ext:\r\n\
+//!      Registrant Email: https://example.uz/contact\r\n"
+//! );
+//!
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new_fn("Ivan\r\nRegistrant Email: forged@example.uz", None));
+//! assert!(vcard
+//!     .to_whois("Registrant", &[])
+//!     .starts_with("Registrant Name: Ivan  Registrant Email: forged@example.uz\r\nRegistrant Organization:\r\n"));
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt::Write;
+
+use crate::{Address, Property, PropertyName, PropertyValue, Telephone, Vcard};
+
+/// The value of a redacted field.
+pub const REDACTED: &str = "REDACTED FOR PRIVACY";
+
+impl Vcard {
+    /// Renders the vCard as a WHOIS contact block, with `prefix` before every key, e.g. `"Registrant"` or
+    /// `"Tech"`. `redacted` holds the full names of the redacted fields, e.g. `"Registrant Email"`, compared
+    /// case-insensitively.
+    ///
+    /// Lines end with CRLF, as in the WHOIS protocol.
+    pub fn to_whois(&self, prefix: &str, redacted: &[&str]) -> String {
+        let first = |name: PropertyName| {
+            self.properties
+                .iter()
+                .find(|property| property.property_name() == name)
+        };
+        let address = first(PropertyName::Adr).map(address).unwrap_or_default();
+        let (phone, phone_ext) = telephone(self, false);
+        let (fax, fax_ext) = telephone(self, true);
+
+        let mut block = Block {
+            prefix,
+            redacted,
+            output: String::new(),
+        };
+        block.field(
+            "Name",
+            first(PropertyName::Fn).and_then(Property::text),
+            None,
+        );
+        block.field(
+            "Organization",
+            first(PropertyName::Org).and_then(Property::text),
+            None,
+        );
+        match address
+            .street_address
+            .lines()
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            [] => block.field("Street", None, None),
+            lines => lines
+                .iter()
+                .for_each(|line| block.field("Street", Some(line), None)),
+        }
+        block.field("City", Some(&address.locality), None);
+        block.field("State/Province", Some(&address.region), None);
+        block.field("Postal Code", Some(&address.postal_code), None);
+        block.field("Country", Some(&address.country), None);
+        block.field("Phone", phone, None);
+        block.field("Phone Ext", phone_ext, Some("Phone"));
+        block.field("Fax", fax, None);
+        block.field("Fax Ext", fax_ext, Some("Fax"));
+        block.field(
+            "Email",
+            first(PropertyName::Email).and_then(Property::text),
+            None,
+        );
+
+        block.output
+    }
+}
+
+struct Block<'a> {
+    prefix: &'a str,
+    redacted: &'a [&'a str],
+    output: String,
+}
+
+impl Block<'_> {
+    /// Writes a field, redacted if its key or the key of the field it belongs to is redacted.
+    fn field(&mut self, key: &str, value: Option<&str>, parent: Option<&str>) {
+        let is_redacted = |key: &str| {
+            let name = format!("{} {key}", self.prefix);
+            self.redacted
+                .iter()
+                .any(|redacted| redacted.eq_ignore_ascii_case(&name))
+        };
+
+        let value: String = match is_redacted(key) || parent.is_some_and(is_redacted) {
+            true => REDACTED.into(),
+            false => value
+                .unwrap_or_default()
+                .chars()
+                .map(|c| if c.is_control() { ' ' } else { c })
+                .collect(),
+        };
+        let _ = match value.is_empty() {
+            true => write!(self.output, "{} {key}:\r\n", self.prefix),
+            false => write!(self.output, "{} {key}: {value}\r\n", self.prefix),
+        };
+    }
+}
+
+/// The address of an `adr` property, with street lines separated by newlines and the country from the `cc`
+/// parameter.
+fn address(property: &Property) -> Address {
+    let mut components: [String; 7] = Default::default();
+    if let [PropertyValue::Structured(values)] = property.values.as_slice() {
+        for (component, value) in components.iter_mut().zip(values) {
+            *component = match value {
+                PropertyValue::String(value) => value.clone(),
+                PropertyValue::Structured(values) => values
+                    .iter()
+                    .filter_map(|value| match value {
+                        PropertyValue::String(value) => Some(value.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ => String::new(),
+            };
+        }
+    }
+
+    let mut address = Address::from(components);
+    if let Some([cc]) = property.parameters.get("cc").map(Vec::as_slice) {
+        address.country = cc.clone();
+    }
+    address
+}
+
+/// The number and extension of the first `tel` with, or without, the fax [`Telephone`] type.
+fn telephone(vcard: &Vcard, fax: bool) -> (Option<&str>, Option<&str>) {
+    let tel = vcard.properties.iter().find(|property| {
+        property.property_name() == PropertyName::Tel
+            && property
+                .parameters
+                .types()
+                .any(|t| matches!(t.as_ref().to_ascii_lowercase().parse(), Ok(Telephone::Fax)))
+                == fax
+    });
+    match tel.and_then(Property::tel_number) {
+        Some((number, ext)) => (Some(number), ext),
+        None => (None, None),
+    }
+}
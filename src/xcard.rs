@@ -0,0 +1,499 @@
+//! xCard ([RFC 6351](https://datatracker.ietf.org/doc/html/rfc6351)) XML serialization and parsing.
+//!
+//! The XML representation maps onto the same [`Vcard`] model as jCard:
+//!
+//! - [`Property::value_type`] is the name of the value element (`<text>`, `<uri>`, `<date>`, ...),
+//! - [`PropertyValue::Structured`] values use the component elements of the property (`<surname>`, `<street>`, ...),
+//! - [`Property::parameters`] are written to the `<parameters>` element,
+//! - the `group` parameter becomes a `<group name="...">` element around the property,
+//! - elements from other XML namespaces are kept as `xml` properties, as described in
+//!   [RFC 6351, Section 6](https://datatracker.ietf.org/doc/html/rfc6351#section-6).
+//!
+//! Date and time values are converted between the basic format used by xCard and the extended format used by jCard.
+//! xCard has no `date-and-or-time` element, so such values are written with the element of their actual type and a
+//! `value` parameter of `date-and-or-time`.
+//!
+//! ```rust
+//! # use vicardi::*;
+//! # fn main() -> anyhow::Result<()> {
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new_fn("John Doe", None));
+//! vcard.push(Property::new_org(PropertyValue::Structured(vec!["Vicardi".into(), "Rust development".into()]), None));
+//! vcard.push(Property::new("bday", None, "date-and-or-time", "--04-12"));
+//!
+//! let xml = vcard.to_xcard()?;
+//!
+//! assert_eq!(
+//!     xml,
+//!     r#"<?xml version="1.0" encoding="UTF-8"?><vcards xmlns="urn:ietf:params:xml:ns:vcard-4.0"><vcard><fn><text>John Doe</text></fn><org><text>Vicardi</text><text>Rust development</text></org><bday><parameters><value><text>date-and-or-time</text></value></parameters><date>--0412</date></bday></vcard></vcards>"#
+//! );
+//! assert_eq!(Vcard::from_xcard(&xml)?, vcard);
+//! assert!(matches!(Vcard::from_xcard(&xml[..100]), Err(Error::Xcard(XcardError::Xml { .. }))));
+//!
+//! let mut dated = Vcard::default();
+//! dated.push(Property::new("bday", None, ValueType::Date, "1985-04-12"));
+//! assert!(dated.to_xcard()?.contains("<bday><date>19850412</date></bday>"));
+//! assert_eq!(Vcard::from_xcard(&dated.to_xcard()?)?, dated);
+//!
+//! vcard.push(Property::new("x-a b", None, ValueType::Text, "v"));
+//! assert_eq!(vcard.to_xcard(), Err(Error::Xcard(XcardError::InvalidName("x-a b".into()))));
+//!
+//! let mut vcard = Vcard::default();
+//! vcard.push(Property::new("xml", None, ValueType::Text, r#"<a xmlns="http://example.com/ns">b</a>"#));
+//! assert_eq!(Vcard::from_xcard(&vcard.to_xcard()?)?, vcard);
+//!
+//! vcard.properties[0] = Property::new("xml", None, ValueType::Text, "</vcard><evil/>");
+//! assert_eq!(vcard.to_xcard(), Err(Error::Xcard(XcardError::InvalidXml("</vcard><evil/>".into()))));
+//! # Ok(())
+//! # }
+//! ```
+use quick_xml::escape::escape;
+use thiserror::Error;
+
+use crate::{
+    datetime::{datetime_format, is_datetime},
+    is_group,
+    xml::{Node, SyntaxError},
+    Parameters, Property, PropertyValue, ValueType, Vcard,
+};
+
+/// The XML namespace of xCard elements.
+pub const NAMESPACE: &str = "urn:ietf:params:xml:ns:vcard-4.0";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum XcardError {
+    #[error("invalid XML at byte {position}: {message}")]
+    Xml { position: u64, message: String },
+    #[error("expected a <vcards> or <vcard> root element in the xCard namespace")]
+    MissingVcard,
+    #[error("expected a single <vcard> element")]
+    MultipleVcards,
+    #[error("property <{0}> has no value")]
+    MissingValue(String),
+    #[error("invalid {value_type} value {value:?}")]
+    InvalidValue { value_type: String, value: String },
+    /// A property, parameter or value type name that cannot be written as an element name.
+    #[error("{0:?} is not a valid vCard name")]
+    InvalidName(String),
+    /// An `xml` property value that is not a single well-formed element from a namespace other than xCard's.
+    #[error("xml property value {0:?} is not a single element from another namespace")]
+    InvalidXml(String),
+}
+
+impl XcardError {
+    /// A stable, kebab-case identifier of the variant, returned by [`Error::code`](crate::Error::code).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Xml { .. } => "xcard-xml",
+            Self::MissingVcard => "xcard-missing-vcard",
+            Self::MultipleVcards => "xcard-multiple-vcards",
+            Self::MissingValue(_) => "xcard-missing-value",
+            Self::InvalidValue { .. } => "xcard-invalid-value",
+            Self::InvalidName(_) => "xcard-invalid-name",
+            Self::InvalidXml(_) => "xcard-invalid-xml",
+        }
+    }
+}
+
+impl From<SyntaxError> for XcardError {
+    fn from(error: SyntaxError) -> Self {
+        Self::Xml {
+            position: error.position,
+            message: error.message,
+        }
+    }
+}
+
+impl Vcard {
+    /// Writes the vCard as an xCard document with a single `<vcard>` in a `<vcards>` root element.
+    ///
+    /// The version is implied by the xCard namespace, so [`Vcard::version`] is not written. Property, parameter and
+    /// value type names become element names, so they must be vCard names: letters, digits and dashes, starting with
+    /// a letter. The values of `xml` properties are written as-is, so each must be a single well-formed element from a
+    /// namespace other than xCard's.
+    pub fn to_xcard(&self) -> Result<String, crate::Error> {
+        Ok(self.write_xcard()?)
+    }
+
+    fn write_xcard(&self) -> Result<String, XcardError> {
+        let mut out =
+            format!(r#"<?xml version="1.0" encoding="UTF-8"?><vcards xmlns="{NAMESPACE}"><vcard>"#);
+
+        let mut open_group: Option<&str> = None;
+        for property in &self.properties {
+            let group = property.group();
+            if group != open_group {
+                if open_group.is_some() {
+                    out.push_str("</group>");
+                }
+                if let Some(group) = group {
+                    if !is_group(group) {
+                        return Err(XcardError::InvalidName(group.to_string()));
+                    }
+                    out.push_str(&format!(r#"<group name="{}">"#, escape(group)));
+                }
+                open_group = group;
+            }
+
+            write_property(property, &mut out)?;
+        }
+        if open_group.is_some() {
+            out.push_str("</group>");
+        }
+
+        out.push_str("</vcard></vcards>");
+        Ok(out)
+    }
+
+    /// Parses an xCard document with a single `<vcard>`, either as the root element or inside a `<vcards>` root.
+    ///
+    /// [`Vcard::version`] is always `"4.0"`, the version of the xCard namespace.
+    pub fn from_xcard(input: &str) -> Result<Self, crate::Error> {
+        Ok(Self::read_xcard(input)?)
+    }
+
+    fn read_xcard(input: &str) -> Result<Self, XcardError> {
+        let root = Node::parse(input)?.ok_or(XcardError::MissingVcard)?;
+
+        let vcard = match (root.is_in(NAMESPACE), root.name.as_str()) {
+            (true, "vcard") => &root,
+            (true, "vcards") => {
+                let mut vcards = root
+                    .children
+                    .iter()
+                    .filter(|child| child.is_in(NAMESPACE) && child.name == "vcard");
+                let vcard = vcards.next().ok_or(XcardError::MissingVcard)?;
+                if vcards.next().is_some() {
+                    return Err(XcardError::MultipleVcards);
+                }
+                vcard
+            }
+            _ => return Err(XcardError::MissingVcard),
+        };
+
+        let mut properties = Vec::with_capacity(vcard.children.len());
+        for child in &vcard.children {
+            match (child.is_in(NAMESPACE), child.name.as_str()) {
+                (true, "group") => {
+                    let name = child.attribute("name").unwrap_or_default();
+                    for grouped in &child.children {
+                        let mut property = read_property(grouped, input)?;
+                        property
+                            .set_group(name)
+                            .map_err(|_| XcardError::InvalidName(name.to_string()))?;
+                        properties.push(property);
+                    }
+                }
+                (true, "version") => {}
+                _ => properties.push(read_property(child, input)?),
+            }
+        }
+
+        Ok(Vcard {
+            version: "4.0".into(),
+            properties,
+        })
+    }
+}
+
+/// The component element names of a structured property, or `None` if the property is not structured.
+///
+/// `org` is structured but its components are all `<text>` elements.
+fn component_names(name: &str) -> Option<&'static [&'static str]> {
+    match name {
+        "n" => Some(&["surname", "given", "additional", "prefix", "suffix"]),
+        "adr" => Some(&[
+            "pobox", "ext", "street", "locality", "region", "code", "country",
+        ]),
+        "gender" => Some(&["sex", "identity"]),
+        "clientpidmap" => Some(&["sourceid", "uri"]),
+        "org" => Some(&["text"]),
+        _ => None,
+    }
+}
+
+/// The value element of a parameter, per the parameter definitions in RFC 6351.
+fn parameter_value_type(key: &str, value: &str) -> &'static str {
+    match key {
+        "pref" | "index" => "integer",
+        "language" => "language-tag",
+        "geo" => "uri",
+        "tz" if value.contains(':') => "uri",
+        _ => "text",
+    }
+}
+
+fn write_element(name: &str, text: &str, out: &mut String) {
+    match text {
+        "" => out.push_str(&format!("<{name}/>")),
+        text => out.push_str(&format!("<{name}>{}</{name}>", escape(text))),
+    }
+}
+
+/// Whether the name is a vCard name (RFC 6350, Section 3.3) that is also an XML element name.
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn check_name(name: &str) -> Result<(), XcardError> {
+    match is_name(name) {
+        true => Ok(()),
+        false => Err(XcardError::InvalidName(name.to_string())),
+    }
+}
+
+fn write_property(property: &Property, out: &mut String) -> Result<(), XcardError> {
+    let name = property.name.to_lowercase();
+
+    // The XML property holds an element from another namespace that is written as-is
+    if name == "xml" {
+        for value in &property.values {
+            let xml = match value {
+                PropertyValue::String(xml) => xml.trim(),
+                value => return Err(XcardError::InvalidXml(value_text(value))),
+            };
+            match Node::parse(xml) {
+                Ok(Some(node))
+                    if node.span == (0, xml.len())
+                        && node.namespace.is_some()
+                        && !node.is_in(NAMESPACE) =>
+                {
+                    out.push_str(xml)
+                }
+                _ => return Err(XcardError::InvalidXml(xml.to_string())),
+            }
+        }
+        return Ok(());
+    }
+
+    check_name(&name)?;
+    check_name(property.value_type.as_str())?;
+
+    out.push_str(&format!("<{name}>"));
+
+    let parameters: Vec<_> = property
+        .parameters
+        .iter()
+        .filter(|(key, _)| *key != "group" && *key != "value")
+        .collect();
+    // xCard has no date-and-or-time element, so the type is kept in the VALUE parameter
+    let date_and_or_time = property.value_type.as_str() == "date-and-or-time";
+    if !parameters.is_empty() || date_and_or_time {
+        out.push_str("<parameters>");
+        for (key, values) in parameters {
+            check_name(key)?;
+            out.push_str(&format!("<{key}>"));
+            for value in values {
+                write_element(parameter_value_type(key, value), value, out);
+            }
+            out.push_str(&format!("</{key}>"));
+        }
+        if date_and_or_time {
+            out.push_str("<value><text>date-and-or-time</text></value>");
+        }
+        out.push_str("</parameters>");
+    }
+
+    for value in &property.values {
+        match (component_names(&name), value) {
+            (Some(names), PropertyValue::Structured(components)) => {
+                for (i, component) in components.iter().enumerate() {
+                    let element = names.get(i).or(names.last()).copied().unwrap_or("text");
+                    match component {
+                        PropertyValue::Structured(values) if !values.is_empty() => {
+                            for value in values {
+                                write_element(element, &value_text(value), out);
+                            }
+                        }
+                        component => write_element(element, &value_text(component), out),
+                    }
+                }
+            }
+            (Some(names), single) if property.value_type == ValueType::Text => {
+                write_element(names[0], &value_text(single), out)
+            }
+            (_, value) => write_value(property.value_type.as_str(), value, out),
+        }
+    }
+
+    out.push_str(&format!("</{name}>"));
+    Ok(())
+}
+
+fn write_value(value_type: &str, value: &PropertyValue, out: &mut String) {
+    match value {
+        PropertyValue::String(string) if is_datetime(value_type) => {
+            let basic =
+                datetime_format(value_type, string, false).unwrap_or_else(|| string.clone());
+            match value_type {
+                // xCard has no date-and-or-time element, the value uses the element of its actual type
+                "date-and-or-time" => match basic.split_once('T') {
+                    Some(("", time)) => write_element("time", time, out),
+                    Some(_) => write_element("date-time", &basic, out),
+                    None => write_element("date", &basic, out),
+                },
+                value_type => write_element(value_type, &basic, out),
+            }
+        }
+        PropertyValue::Structured(values) => values
+            .iter()
+            .for_each(|value| write_value(value_type, value, out)),
+        value => write_element(value_type, &value_text(value), out),
+    }
+}
+
+fn value_text(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::String(string) => string.clone(),
+        PropertyValue::Bool(boolean) => boolean.to_string(),
+        PropertyValue::Integer(int) => int.to_string(),
+        PropertyValue::Float(float) => float.to_string(),
+        PropertyValue::Structured(values) => {
+            values.iter().map(value_text).collect::<Vec<_>>().join(",")
+        }
+    }
+}
+
+fn read_property(node: &Node, input: &str) -> Result<Property, XcardError> {
+    if !node.is_in(NAMESPACE) {
+        return Ok(Property::new(
+            "xml",
+            None,
+            "text",
+            &input[node.span.0..node.span.1],
+        ));
+    }
+
+    let name = node.name.clone();
+    let mut parameters = Parameters::new();
+    let mut values = Vec::new();
+    let mut value_type = None;
+
+    let value_nodes: Vec<&Node> = node
+        .children
+        .iter()
+        .filter(|child| child.is_in(NAMESPACE) && child.name != "parameters")
+        .collect();
+
+    for child in node
+        .children
+        .iter()
+        .filter(|child| child.name == "parameters")
+    {
+        for parameter in &child.children {
+            parameters.insert(
+                parameter.name.clone(),
+                parameter
+                    .children
+                    .iter()
+                    .map(|value| value.text.clone())
+                    .collect(),
+            );
+        }
+    }
+
+    let declared_type = match parameters.shift_remove("value").as_deref() {
+        Some([value_type]) => Some(value_type.to_lowercase()),
+        _ => None,
+    };
+
+    match component_names(&name) {
+        Some(names)
+            if value_nodes
+                .iter()
+                .any(|child| names.contains(&child.name.as_str())) =>
+        {
+            let mut components: Vec<PropertyValue> = match name.as_str() {
+                "org" => value_nodes
+                    .iter()
+                    .map(|child| PropertyValue::String(child.text.clone()))
+                    .collect(),
+                _ => names
+                    .iter()
+                    .map(|component| {
+                        let mut values: Vec<PropertyValue> = value_nodes
+                            .iter()
+                            .filter(|child| child.name == *component)
+                            .map(|child| PropertyValue::String(child.text.clone()))
+                            .collect();
+                        match values.len() {
+                            0 => PropertyValue::String(String::new()),
+                            1 => values.remove(0),
+                            _ => PropertyValue::Structured(values),
+                        }
+                    })
+                    .collect(),
+            };
+
+            // Trailing components that are missing from the XML are not part of the value
+            let present = names
+                .iter()
+                .rposition(|component| value_nodes.iter().any(|child| child.name == *component))
+                .map_or(components.len(), |i| i + 1);
+            if name != "org" {
+                components.truncate(present);
+            }
+
+            value_type = Some("text".to_string());
+            values.push(match components.len() {
+                1 => components.remove(0),
+                _ => PropertyValue::Structured(components),
+            });
+        }
+        _ => {
+            for child in value_nodes {
+                let (child_type, value) = read_value(child, declared_type.as_deref())?;
+                value_type.get_or_insert(child_type);
+                values.push(value);
+            }
+        }
+    }
+
+    let Some(value_type) = value_type else {
+        return Err(XcardError::MissingValue(name));
+    };
+
+    Ok(Property::new_multivalued(
+        name, parameters, value_type, values,
+    ))
+}
+
+/// Reads a value element, whose name is the value type unless the VALUE parameter declares a date-and-or-time.
+fn read_value(
+    node: &Node,
+    declared_type: Option<&str>,
+) -> Result<(String, PropertyValue), XcardError> {
+    let invalid = || XcardError::InvalidValue {
+        value_type: node.name.clone(),
+        value: node.text.clone(),
+    };
+
+    let value_type = match (declared_type, node.name.as_str()) {
+        (Some("date-and-or-time"), "date" | "time" | "date-time") => "date-and-or-time",
+        (_, value_type) => value_type,
+    };
+
+    let value = match (value_type, node.name.as_str()) {
+        ("date-and-or-time", "time") => PropertyValue::String(format!(
+            "T{}",
+            datetime_format("time", &node.text, true).ok_or_else(invalid)?
+        )),
+        ("date-and-or-time", element) => {
+            PropertyValue::String(datetime_format(element, &node.text, true).ok_or_else(invalid)?)
+        }
+        (value_type, _) if is_datetime(value_type) => PropertyValue::String(
+            datetime_format(value_type, &node.text, true).ok_or_else(invalid)?,
+        ),
+        ("boolean", _) => match node.text.trim() {
+            "true" | "1" => PropertyValue::Bool(true),
+            "false" | "0" => PropertyValue::Bool(false),
+            _ => return Err(invalid()),
+        },
+        ("integer", _) => PropertyValue::Integer(node.text.trim().parse().map_err(|_| invalid())?),
+        ("float", _) => PropertyValue::Float(node.text.trim().parse().map_err(|_| invalid())?),
+        _ => PropertyValue::String(node.text.clone()),
+    };
+
+    Ok((value_type.to_string(), value))
+}
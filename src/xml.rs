@@ -0,0 +1,153 @@
+//! A minimal namespace-aware XML reader
+use quick_xml::{
+    events::{BytesStart, Event},
+    name::ResolveResult,
+    NsReader,
+};
+
+/// A minimal XML element tree.
+pub(crate) struct Node {
+    /// The namespace URI of the element, if it has one.
+    pub(crate) namespace: Option<String>,
+    /// The local name of the element.
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<Node>,
+    pub(crate) text: String,
+    /// Byte range of the element in the input.
+    pub(crate) span: (usize, usize),
+}
+
+/// Malformed XML.
+pub(crate) struct SyntaxError {
+    /// The byte position of the error in the input.
+    pub(crate) position: u64,
+    pub(crate) message: String,
+}
+
+impl Node {
+    fn new(
+        namespace: ResolveResult,
+        start: &BytesStart,
+        offset: usize,
+    ) -> Result<Self, quick_xml::Error> {
+        let namespace = match namespace {
+            ResolveResult::Bound(ns) => Some(String::from_utf8_lossy(ns.as_ref()).into_owned()),
+            _ => None,
+        };
+
+        let attributes = start
+            .attributes()
+            .map(|attribute| {
+                let attribute = attribute?;
+                Ok((
+                    String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned(),
+                    attribute.unescape_value()?.into_owned(),
+                ))
+            })
+            .collect::<Result<_, quick_xml::Error>>()?;
+
+        Ok(Self {
+            namespace,
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attributes,
+            children: Vec::new(),
+            text: String::new(),
+            span: (offset, offset),
+        })
+    }
+
+    /// Whether the element is in the namespace.
+    pub(crate) fn is_in(&self, namespace: &str) -> bool {
+        self.namespace.as_deref() == Some(namespace)
+    }
+
+    /// The children in the namespace with the local name.
+    pub(crate) fn children_named<'a>(
+        &'a self,
+        namespace: &'a str,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a Node> {
+        self.children
+            .iter()
+            .filter(move |child| child.is_in(namespace) && child.name == name)
+    }
+
+    pub(crate) fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parses the root element of a document, or `None` if the document has no elements.
+    pub(crate) fn parse(input: &str) -> Result<Option<Node>, SyntaxError> {
+        let mut reader = NsReader::from_str(input);
+        let mut stack: Vec<Node> = Vec::new();
+
+        let xml_error = |reader: &NsReader<&[u8]>, error: quick_xml::Error| SyntaxError {
+            position: reader.error_position(),
+            message: error.to_string(),
+        };
+
+        loop {
+            let offset = reader.buffer_position() as usize;
+            let (namespace, event) = match reader.read_resolved_event() {
+                Ok(resolved) => resolved,
+                Err(error) => return Err(xml_error(&reader, error)),
+            };
+
+            let finished = match event {
+                Event::Start(start) => {
+                    let node = Node::new(namespace, &start, offset)
+                        .map_err(|error| xml_error(&reader, error))?;
+                    stack.push(node);
+                    None
+                }
+                Event::Empty(start) => {
+                    let mut node = Node::new(namespace, &start, offset)
+                        .map_err(|error| xml_error(&reader, error))?;
+                    node.span.1 = reader.buffer_position() as usize;
+                    Some(node)
+                }
+                Event::End(_) => stack.pop().map(|mut node| {
+                    node.span.1 = reader.buffer_position() as usize;
+                    node
+                }),
+                Event::Text(text) => {
+                    if let Some(node) = stack.last_mut() {
+                        let text = text.unescape().map_err(|error| xml_error(&reader, error))?;
+                        node.text.push_str(&text);
+                    }
+                    None
+                }
+                Event::CData(data) => {
+                    if let Some(node) = stack.last_mut() {
+                        node.text.push_str(&String::from_utf8_lossy(&data));
+                    }
+                    None
+                }
+                Event::Eof => {
+                    return match stack.last() {
+                        Some(node) => Err(SyntaxError {
+                            position: reader.buffer_position(),
+                            message: format!(
+                                "unexpected end of input, <{}> is not closed",
+                                node.name
+                            ),
+                        }),
+                        None => Ok(None),
+                    }
+                }
+                _ => None,
+            };
+
+            if let Some(node) = finished {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => return Ok(Some(node)),
+                }
+            }
+        }
+    }
+}